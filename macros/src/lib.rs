@@ -0,0 +1,179 @@
+use proc_macro::TokenStream;
+use quote::quote;
+
+mod options;
+
+#[proc_macro_attribute]
+pub fn kernel_test(attribute: TokenStream, item: TokenStream) -> TokenStream {
+    let mut options = options::Options::default();
+    let parser = syn::meta::parser(|meta| options.parse(meta));
+
+    syn::parse_macro_input!(attribute with parser);
+    let function = syn::parse_macro_input!(item as syn::ItemFn);
+
+    expand(options, function)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_attribute]
+pub fn kernel_bench(attribute: TokenStream, item: TokenStream) -> TokenStream {
+    let mut options = options::Options::default();
+    let parser = syn::meta::parser(|meta| options.parse(meta));
+
+    syn::parse_macro_input!(attribute with parser);
+    let function = syn::parse_macro_input!(item as syn::ItemFn);
+
+    expand_bench(options, function)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(
+    options: options::Options,
+    function: syn::ItemFn,
+) -> syn::Result<proc_macro2::TokenStream> {
+    check_signature(&function.sig)?;
+
+    let syn::ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = function;
+    let name = &sig.ident;
+    let modifiers = options.modifiers();
+    let host_attributes = options.host_attributes();
+
+    Ok(quote! {
+        #[cfg(not(target_os = "none"))]
+        #(#attrs)*
+        #[test]
+        #[allow(non_snake_case)]
+        #host_attributes
+        #vis #sig #block
+
+        #[cfg(target_os = "none")]
+        #(#attrs)*
+        #[test_case]
+        #[allow(non_upper_case_globals)]
+        #vis const #name: ::ferros::libs::testing::KernelTest = {
+            #[allow(non_snake_case)]
+            #sig #block
+
+            ::ferros::libs::testing::KernelTest::new(
+                concat!(module_path!(), "::", stringify!(#name)),
+                #name,
+            )
+            #modifiers
+        };
+    })
+}
+
+fn expand_bench(
+    options: options::Options,
+    function: syn::ItemFn,
+) -> syn::Result<proc_macro2::TokenStream> {
+    check_bench_signature(&function.sig)?;
+
+    if options.should_panic() {
+        return Err(syn::Error::new_spanned(
+            &function.sig,
+            "kernel benchmarks cannot use `should_panic`",
+        ));
+    }
+
+    let syn::ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = function;
+    let name = &sig.ident;
+    let modifiers = options.modifiers();
+    let host_attributes = options.host_attributes();
+
+    Ok(quote! {
+        #[cfg(not(target_os = "none"))]
+        #(#attrs)*
+        #[test]
+        #[allow(non_snake_case)]
+        #host_attributes
+        #vis fn #name() {
+            #[allow(non_snake_case)]
+            #sig #block
+
+            ::ferros::libs::testing::run_bench(#name);
+        }
+
+        #[cfg(target_os = "none")]
+        #(#attrs)*
+        #[test_case]
+        #[allow(non_upper_case_globals)]
+        #vis const #name: ::ferros::libs::testing::KernelBench = {
+            #[allow(non_snake_case)]
+            #sig #block
+
+            ::ferros::libs::testing::KernelBench::new(
+                concat!(module_path!(), "::", stringify!(#name)),
+                #name,
+            )
+            #modifiers
+        };
+    })
+}
+
+fn check_bench_signature(signature: &syn::Signature) -> syn::Result<()> {
+    let error =
+        |tokens: &dyn quote::ToTokens, message: &str| Err(syn::Error::new_spanned(tokens, message));
+
+    if let Some(asyncness) = &signature.asyncness {
+        return error(asyncness, "kernel benchmarks cannot be async");
+    }
+
+    if !signature.generics.params.is_empty() {
+        return error(&signature.generics, "kernel benchmarks cannot be generic");
+    }
+
+    if signature.inputs.len() != 1 {
+        return error(
+            &signature.inputs,
+            "kernel benchmarks take a single `&mut Bencher` argument",
+        );
+    }
+
+    match &signature.output {
+        syn::ReturnType::Type(_, output) if !is_unit(output) => {
+            error(output, "kernel benchmarks must return `()`")
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check_signature(signature: &syn::Signature) -> syn::Result<()> {
+    let error =
+        |tokens: &dyn quote::ToTokens, message: &str| Err(syn::Error::new_spanned(tokens, message));
+
+    if let Some(asyncness) = &signature.asyncness {
+        return error(asyncness, "kernel tests cannot be async");
+    }
+
+    if !signature.generics.params.is_empty() {
+        return error(&signature.generics, "kernel tests cannot be generic");
+    }
+
+    if !signature.inputs.is_empty() {
+        return error(&signature.inputs, "kernel tests cannot take arguments");
+    }
+
+    match &signature.output {
+        syn::ReturnType::Type(_, output) if !is_unit(output) => {
+            error(output, "kernel tests must return `()`")
+        }
+        _ => Ok(()),
+    }
+}
+
+fn is_unit(output: &syn::Type) -> bool {
+    matches!(output, syn::Type::Tuple(tuple) if tuple.elems.is_empty())
+}
@@ -0,0 +1,95 @@
+use quote::quote;
+
+#[derive(Default)]
+pub struct Options {
+    ignore: bool,
+    should_panic: Option<Option<syn::LitStr>>,
+    timeout: Option<syn::LitInt>,
+    tags: Vec<syn::LitStr>,
+}
+
+impl Options {
+    pub fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("ignore") {
+            self.ignore = true;
+        } else if meta.path.is_ident("should_panic") {
+            let mut expected = None;
+
+            if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|nested| match nested.path.is_ident("expected") {
+                    true => {
+                        expected = Some(nested.value()?.parse()?);
+                        Ok(())
+                    }
+                    false => Err(nested.error("expected `expected = \"...\"`")),
+                })?;
+            }
+
+            self.should_panic = Some(expected);
+        } else if meta.path.is_ident("timeout") {
+            let timeout: syn::LitInt = meta.value()?.parse()?;
+            timeout.base10_parse::<u64>()?;
+            self.timeout = Some(timeout);
+        } else if meta.path.is_ident("tags") {
+            meta.parse_nested_meta(|nested| match nested.path.get_ident() {
+                Some(tag) => {
+                    self.tags
+                        .push(syn::LitStr::new(&tag.to_string(), tag.span()));
+                    Ok(())
+                }
+                None => Err(nested.error("expected a tag name")),
+            })?;
+        } else {
+            return Err(meta.error("unsupported kernel_test option"));
+        }
+
+        Ok(())
+    }
+
+    pub fn should_panic(&self) -> bool {
+        self.should_panic.is_some()
+    }
+
+    pub fn modifiers(&self) -> proc_macro2::TokenStream {
+        let mut modifiers = proc_macro2::TokenStream::new();
+
+        if self.ignore {
+            modifiers.extend(quote!(.ignore()));
+        }
+
+        match &self.should_panic {
+            Some(Some(expected)) => modifiers.extend(quote!(.should_panic(Some(#expected)))),
+            Some(None) => modifiers.extend(quote!(.should_panic(None))),
+            None => {}
+        }
+
+        if let Some(timeout) = &self.timeout {
+            modifiers.extend(quote!(.timeout(#timeout)));
+        }
+
+        if !self.tags.is_empty() {
+            let tags = &self.tags;
+            modifiers.extend(quote!(.tags(&[#(#tags),*])));
+        }
+
+        modifiers
+    }
+
+    pub fn host_attributes(&self) -> proc_macro2::TokenStream {
+        let mut attributes = proc_macro2::TokenStream::new();
+
+        if self.ignore {
+            attributes.extend(quote!(#[ignore]));
+        }
+
+        match &self.should_panic {
+            Some(Some(expected)) => {
+                attributes.extend(quote!(#[should_panic(expected = #expected)]))
+            }
+            Some(None) => attributes.extend(quote!(#[should_panic])),
+            None => {}
+        }
+
+        attributes
+    }
+}
@@ -1,3 +1,10 @@
 #!/usr/bin/env bash
 
+set -e
+
+cd "$(dirname "$0")/.."
+
+cargo build
+scripts/embed-symbols.sh target/x86_64-unknown-none/debug/ferros ||
+    echo "warning: could not embed symbols; backtraces will not show names" >&2
 cargo bootimage
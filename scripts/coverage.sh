@@ -0,0 +1,44 @@
+#!/usr/bin/env bash
+
+# Runs the kernel tests with coverage instrumentation and writes an lcov report:
+#
+#   scripts/coverage.sh [cargo test arguments...]
+#
+# Needs `rustup component add llvm-tools`. The report is target/coverage/lcov.info.
+
+set -u
+
+root="$(cd "$(dirname "$0")/.." && pwd)"
+output="$root/target/coverage"
+sysroot="$(rustc --print sysroot)"
+host="$(rustc -vV | sed -n 's/^host: //p')"
+tools="$sysroot/lib/rustlib/$host/bin"
+
+rm -rf "$output"
+mkdir -p "$output"
+
+cd "$root"
+FERROS_COVERAGE_DIR="$output" cargo test --features coverage \
+    --config 'build.rustflags = ["-Cinstrument-coverage", "-Zno-profiler-runtime"]' "$@"
+status=$?
+
+profiles=("$output"/*.profraw)
+if [ ! -s "${profiles[0]}" ]; then
+    echo "no coverage profiles were written" >&2
+    exit 1
+fi
+
+"$tools/llvm-profdata" merge -sparse "${profiles[@]}" -o "$output/ferros.profdata" || exit 1
+
+objects=()
+while read -r object; do
+    objects+=("--object" "$object")
+done < <(sort -u "$output/objects")
+
+"$tools/llvm-cov" export -format=lcov \
+    -instr-profile="$output/ferros.profdata" \
+    -ignore-filename-regex='/(\.cargo|rustlib)/' \
+    "${objects[@]}" > "$output/lcov.info" || exit 1
+
+echo "wrote $output/lcov.info" >&2
+exit $status
@@ -0,0 +1,18 @@
+#!/usr/bin/env bash
+
+# Pretty-prints a crash dump on the host:
+#
+#   scripts/crashdump.sh serial.log
+#
+# Takes a serial log with an armored dump, a raw dump, or - for stdin. Cargo is
+# started outside the repository so that .cargo/config.toml does not apply.
+
+root="$(cd "$(dirname "$0")/.." && pwd)"
+host="$(rustc -vV | sed -n 's/^host: //p')"
+
+input="$1"
+if [ -f "$input" ]; then
+    input="$(cd "$(dirname "$input")" && pwd)/$(basename "$input")"
+fi
+
+cd / && exec cargo run --quiet --manifest-path "$root/tools/crashdump/Cargo.toml" --target "$host" -- "$input"
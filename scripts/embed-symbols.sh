@@ -0,0 +1,53 @@
+#!/usr/bin/env bash
+
+# Post-link step: writes the kernel's function symbols into its reserved .symbols
+# section so panic backtraces can print `function+offset` frames:
+#
+#   scripts/embed-symbols.sh target/x86_64-unknown-none/debug/ferros
+#
+# The table is a header line followed by one `address name` line per function,
+# sorted by address. Needs `rustup component add llvm-tools`.
+
+set -eu
+
+kernel="$1"
+sysroot="$(rustc --print sysroot)"
+host="$(rustc -vV | sed -n 's/^host: //p')"
+tools="$sysroot/lib/rustlib/$host/bin"
+
+if [ ! -x "$tools/llvm-objdump" ] || [ ! -x "$tools/llvm-objcopy" ]; then
+    echo "llvm-tools not found; run rustup component add llvm-tools" >&2
+    exit 1
+fi
+
+size="$("$tools/llvm-objdump" --section-headers "$kernel" | awk '$2 == ".symbols" { print $3 }')"
+if [ -z "$size" ]; then
+    echo "$kernel has no .symbols section" >&2
+    exit 0
+fi
+
+table="$(mktemp)"
+trap 'rm -f "$table"' EXIT
+
+{
+    echo "ferros symbols"
+    "$tools/llvm-nm" --defined-only --demangle --numeric-sort "$kernel" | awk '
+        $2 ~ /^[tTwW]$/ {
+            address = $1
+            sub(/^0+/, "", address)
+            name = $0
+            sub(/^[^ ]+ [^ ]+ /, "", name)
+            sub(/::h[0-9a-f]+$/, "", name)
+            print address, name
+        }
+    '
+} > "$table"
+
+# The rest of the section stays zero, which terminates the table.
+if [ "$(wc -c < "$table")" -ge $((16#$size)) ]; then
+    echo "symbol table does not fit in .symbols; raise SYMBOL_TABLE_SIZE" >&2
+    exit 1
+fi
+
+truncate -s $((16#$size)) "$table"
+"$tools/llvm-objcopy" --update-section .symbols="$table" "$kernel"
@@ -0,0 +1,50 @@
+#!/usr/bin/env bash
+
+# Cargo runner: QEMU options are passed through to bootimage, every other
+# argument becomes part of the kernel command line exposed through fw_cfg.
+
+kernel="$1"
+shift
+
+qemu_args=()
+command_line=()
+
+while [ $# -gt 0 ]; do
+    case "$1" in
+        --exact | --exit-on-timeout | --ignored | --include-ignored | --update-snapshots | --bench)
+            command_line+=("$1")
+            ;;
+        --baseline=*)
+            qemu_args+=("-fw_cfg" "name=opt/ferros/baseline,file=${1#--baseline=}")
+            ;;
+        -*)
+            qemu_args+=("$1")
+            if [ $# -gt 1 ] && [[ "$2" != -* ]]; then
+                qemu_args+=("$2")
+                shift
+            fi
+            ;;
+        *)
+            command_line+=("$1")
+            ;;
+    esac
+    shift
+done
+
+if [ ${#command_line[@]} -gt 0 ]; then
+    qemu_args+=("-fw_cfg" "name=opt/ferros/cmdline,string=${command_line[*]}")
+fi
+
+# Set by scripts/coverage.sh: collect each kernel's profile through isa-debugcon.
+if [ -n "$FERROS_COVERAGE_DIR" ]; then
+    name="$(basename "$kernel")"
+    echo "$kernel" >> "$FERROS_COVERAGE_DIR/objects"
+    qemu_args+=("-debugcon" "file:$FERROS_COVERAGE_DIR/$name.profraw" "-global" "isa-debugcon.iobase=0xe9")
+fi
+
+# Without llvm-tools the kernel still runs, with `<unknown>` frames in backtraces.
+if ! "$(dirname "$0")/embed-symbols.sh" "$kernel"; then
+    echo "warning: could not embed symbols into $kernel; backtraces will not show names" >&2
+fi
+
+exec bootimage runner "$kernel" "${qemu_args[@]}"
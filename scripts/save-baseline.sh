@@ -0,0 +1,15 @@
+#!/usr/bin/env bash
+
+# Writes a benchmark baseline from a log produced with `--bench`:
+#
+#   cargo test -- --bench | scripts/save-baseline.sh target/bench-baseline.txt
+#
+# Compare a later run against it with `cargo test -- --bench --baseline=target/bench-baseline.txt`.
+
+baseline="${1:-target/bench-baseline.txt}"
+
+awk '
+    /\[ok\] bench: / { name = $1; sub(/\.+$/, "", name); print name, $4 }
+' /dev/stdin > "$baseline"
+
+echo "saved $(wc -l < "$baseline") benchmarks to $baseline" >&2
@@ -0,0 +1,10 @@
+#!/usr/bin/env bash
+
+# Runs the hardware-independent unit tests with the standard test harness on
+# the host. Cargo is started outside the repository so that .cargo/config.toml
+# (custom target and build-std) does not apply.
+
+root="$(cd "$(dirname "$0")/.." && pwd)"
+host="$(rustc +nightly -vV | sed -n 's/^host: //p')"
+
+cd / && exec cargo +nightly test --manifest-path "$root/Cargo.toml" --lib --target "$host" "$@"
@@ -0,0 +1,11 @@
+#!/usr/bin/env bash
+
+# Rewrites snapshot files from a test log produced with `--update-snapshots`:
+#
+#   cargo test -- --update-snapshots | scripts/update-snapshots.sh
+
+awk '
+    /^--- snapshot .* ---\r?$/ { path = $3; print "updated " path > "/dev/stderr"; next }
+    /^--- end snapshot ---\r?$/ { close(path); path = ""; next }
+    path != "" { sub(/\r$/, ""); print > path }
+' "${1:-/dev/stdin}"
@@ -0,0 +1,94 @@
+use x86_64::instructions::port::Port;
+
+const SELECTOR_PORT: u16 = 0x510;
+const DATA_PORT: u16 = 0x511;
+
+const SIGNATURE_SELECTOR: u16 = 0x0000;
+const FILE_DIRECTORY_SELECTOR: u16 = 0x0019;
+
+const SIGNATURE: [u8; 4] = *b"QEMU";
+const FILE_NAME_SIZE: usize = 56;
+
+struct File {
+    size: u32,
+    selector: u16,
+}
+
+pub fn is_present() -> bool {
+    select(SIGNATURE_SELECTOR);
+    read_bytes::<4>() == SIGNATURE
+}
+
+pub fn read_file(name: &str, buffer: &mut [u8]) -> Option<usize> {
+    let file = find(name)?;
+    let length = core::cmp::min(file.size as usize, buffer.len());
+
+    select(file.selector);
+
+    for byte in buffer[..length].iter_mut() {
+        *byte = read();
+    }
+
+    Some(length)
+}
+
+fn find(name: &str) -> Option<File> {
+    if !is_present() {
+        return None;
+    }
+
+    select(FILE_DIRECTORY_SELECTOR);
+    let count = u32::from_be_bytes(read_bytes());
+
+    for _ in 0..count {
+        let size = u32::from_be_bytes(read_bytes());
+        let selector = u16::from_be_bytes(read_bytes());
+        let _reserved = read_bytes::<2>();
+        let file_name = read_bytes::<FILE_NAME_SIZE>();
+
+        let length = file_name
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(FILE_NAME_SIZE);
+
+        if &file_name[..length] == name.as_bytes() {
+            return Some(File { size, selector });
+        }
+    }
+
+    None
+}
+
+fn select(selector: u16) {
+    unsafe { Port::<u16>::new(SELECTOR_PORT).write(selector) }
+}
+
+fn read() -> u8 {
+    unsafe { Port::<u8>::new(DATA_PORT).read() }
+}
+
+fn read_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0; N];
+
+    for byte in bytes.iter_mut() {
+        *byte = read();
+    }
+
+    bytes
+}
+
+#[cfg(all(test, target_os = "none"))]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_is_present() {
+        assert!(is_present());
+    }
+
+    #[test_case]
+    fn test_read_missing_file() {
+        let mut buffer = [0; 16];
+        assert_eq!(read_file("opt/ferros/missing", &mut buffer), None);
+    }
+}
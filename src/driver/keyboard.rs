@@ -0,0 +1,155 @@
+use crate::nucleus::debug;
+use crate::nucleus::interrupt::pic;
+use x86_64::instructions::port::Port;
+use x86_64::structures::idt::InterruptStackFrame;
+
+const DATA_PORT: u16 = 0x60;
+const STATUS_PORT: u16 = 0x64;
+
+const OUTPUT_FULL: u8 = 1 << 0;
+const AUXILIARY_DATA: u8 = 1 << 5;
+
+const EXTENDED: u8 = 0xe0;
+const RELEASED: u8 = 0x80;
+const LEFT_SHIFT: u8 = 0x2a;
+const RIGHT_SHIFT: u8 = 0x36;
+const LEFT_SHIFT_RELEASED: u8 = LEFT_SHIFT | RELEASED;
+const RIGHT_SHIFT_RELEASED: u8 = RIGHT_SHIFT | RELEASED;
+
+pub const IRQ: u8 = 1;
+
+/// Scancode set 1 make codes up to the space bar, without and with shift held.
+const UNSHIFTED: &[u8; 0x3a] =
+    b"\0\x1b1234567890-=\x08\tqwertyuiop[]\n\0asdfghjkl;'`\0\\zxcvbnm,./\0*\0 ";
+const SHIFTED: &[u8; 0x3a] =
+    b"\0\x1b!@#$%^&*()_+\x08\tQWERTYUIOP{}\n\0ASDFGHJKL:\"~\0|ZXCVBNM<>?\0*\0 ";
+
+static DECODER: spin::Mutex<Decoder> = spin::Mutex::new(Decoder::new());
+pub type BreakHook = fn(u8) -> bool;
+
+static BREAK_HOOK: spin::Mutex<Option<BreakHook>> = spin::Mutex::new(None);
+
+/// Turns scancode set 1 into ASCII, tracking shift. Extended keys and keys without an ASCII
+/// meaning are dropped.
+pub struct Decoder {
+    shift: bool,
+    extended: bool,
+}
+
+impl Decoder {
+    pub const fn new() -> Self {
+        Self {
+            shift: false,
+            extended: false,
+        }
+    }
+
+    pub fn decode(&mut self, scancode: u8) -> Option<u8> {
+        if scancode == EXTENDED {
+            self.extended = true;
+            return None;
+        }
+
+        if core::mem::take(&mut self.extended) {
+            return None;
+        }
+
+        match scancode {
+            LEFT_SHIFT | RIGHT_SHIFT => self.shift = true,
+            LEFT_SHIFT_RELEASED | RIGHT_SHIFT_RELEASED => self.shift = false,
+            _ => {}
+        }
+
+        let table = match self.shift {
+            true => SHIFTED,
+            false => UNSHIFTED,
+        };
+
+        table
+            .get(scancode as usize)
+            .copied()
+            .filter(|byte| *byte != 0)
+    }
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Polls the PS/2 controller, so it also works with interrupts disabled.
+pub fn read_scancode() -> Option<u8> {
+    let status = unsafe { Port::<u8>::new(STATUS_PORT).read() };
+
+    if status & OUTPUT_FULL == 0 {
+        return None;
+    }
+
+    let data = unsafe { Port::<u8>::new(DATA_PORT).read() };
+
+    match status & AUXILIARY_DATA {
+        0 => Some(data),
+        _ => None,
+    }
+}
+
+pub fn read_byte() -> Option<u8> {
+    let scancode = read_scancode()?;
+    DECODER.lock().decode(scancode)
+}
+
+/// Called with every scancode received by the interrupt handler. Returning `true` raises a debug
+/// exception as soon as the interrupted code resumes, with that code's registers.
+pub fn set_break_hook(hook: Option<BreakHook>) {
+    x86_64::instructions::interrupts::without_interrupts(|| *BREAK_HOOK.lock() = hook);
+}
+
+pub fn enable_interrupts() {
+    pic::enable(IRQ);
+}
+
+pub(crate) extern "x86-interrupt" fn interrupt_handler(mut stack_frame: InterruptStackFrame) {
+    let hook = BREAK_HOOK.try_lock().and_then(|hook| *hook);
+
+    if let (Some(scancode), Some(hook)) = (read_scancode(), hook) {
+        if hook(scancode) {
+            debug::trap_on_return(&mut stack_frame);
+        }
+    }
+
+    pic::end_of_interrupt(IRQ);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_decode() {
+        let mut decoder = Decoder::new();
+
+        assert_eq!(decoder.decode(0x2d), Some(b'x'));
+        assert_eq!(decoder.decode(0x2d | RELEASED), None);
+        assert_eq!(decoder.decode(0x39), Some(b' '));
+        assert_eq!(decoder.decode(0x0b), Some(b'0'));
+        assert_eq!(decoder.decode(0x1c), Some(b'\n'));
+        assert_eq!(decoder.decode(0x3b), None);
+    }
+
+    #[kernel_test]
+    fn test_decode_shift_and_extended() {
+        let mut decoder = Decoder::new();
+
+        assert_eq!(decoder.decode(LEFT_SHIFT), None);
+        assert_eq!(decoder.decode(0x1e), Some(b'A'));
+        assert_eq!(decoder.decode(0x02), Some(b'!'));
+        assert_eq!(decoder.decode(LEFT_SHIFT_RELEASED), None);
+        assert_eq!(decoder.decode(0x1e), Some(b'a'));
+
+        assert_eq!(decoder.decode(EXTENDED), None);
+        assert_eq!(decoder.decode(0x1c), None);
+        assert_eq!(decoder.decode(0x1c), Some(b'\n'));
+    }
+}
@@ -1 +1,23 @@
+pub mod fw_cfg;
+pub mod keyboard;
+pub mod serial;
 pub mod vga;
+
+/// Logs to the kernel message ring and prints to serial and VGA from panic and fault handlers that
+/// never return to the interrupted code, even if that code held a console lock.
+#[macro_export]
+macro_rules! emergency_print {
+    ($($arg:tt)*) => ($crate::driver::_emergency_print(format_args!($($arg)*)));
+}
+
+#[macro_export]
+macro_rules! emergency_println {
+    () => ($crate::emergency_print!("\n"));
+    ($($arg:tt)*) => ($crate::emergency_print!("{}\n", format_args!($($arg)*)));
+}
+
+pub fn _emergency_print(args: core::fmt::Arguments) {
+    crate::libs::dmesg::print(args);
+    serial::_emergency_print(args);
+    vga::_emergency_print(args);
+}
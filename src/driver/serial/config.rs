@@ -0,0 +1,118 @@
+use super::SerialError;
+use core::convert::TryFrom;
+
+const BASE_CLOCK: u32 = 115_200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataBits {
+    Five = 0x00,
+    Six = 0x01,
+    Seven = 0x02,
+    Eight = 0x03,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    None = 0x00,
+    Odd = 0x08,
+    Even = 0x18,
+    Mark = 0x28,
+    Space = 0x38,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopBits {
+    One = 0x00,
+    Two = 0x04,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    baud_rate: u32,
+    data_bits: DataBits,
+    parity: Parity,
+    stop_bits: StopBits,
+}
+
+impl Config {
+    pub const fn new() -> Self {
+        Self {
+            baud_rate: BASE_CLOCK,
+            data_bits: DataBits::Eight,
+            parity: Parity::None,
+            stop_bits: StopBits::One,
+        }
+    }
+
+    pub const fn baud_rate(mut self, baud_rate: u32) -> Self {
+        self.baud_rate = baud_rate;
+        self
+    }
+
+    pub const fn data_bits(mut self, data_bits: DataBits) -> Self {
+        self.data_bits = data_bits;
+        self
+    }
+
+    pub const fn parity(mut self, parity: Parity) -> Self {
+        self.parity = parity;
+        self
+    }
+
+    pub const fn stop_bits(mut self, stop_bits: StopBits) -> Self {
+        self.stop_bits = stop_bits;
+        self
+    }
+
+    pub(super) fn divisor(&self) -> Result<u16, SerialError> {
+        let invalid = SerialError::InvalidBaudRate(self.baud_rate);
+
+        if self.baud_rate == 0 || !BASE_CLOCK.is_multiple_of(self.baud_rate) {
+            return Err(invalid);
+        }
+
+        u16::try_from(BASE_CLOCK / self.baud_rate).map_err(|_| invalid)
+    }
+
+    pub(super) fn line_control(&self) -> u8 {
+        self.data_bits as u8 | self.stop_bits as u8 | self.parity as u8
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_divisor() {
+        assert_eq!(Config::new().divisor(), Ok(1));
+        assert_eq!(Config::new().baud_rate(9_600).divisor(), Ok(12));
+        assert_eq!(Config::new().baud_rate(50).divisor(), Ok(2_304));
+        assert_eq!(
+            Config::new().baud_rate(7).divisor(),
+            Err(SerialError::InvalidBaudRate(7))
+        );
+        assert_eq!(
+            Config::new().baud_rate(0).divisor(),
+            Err(SerialError::InvalidBaudRate(0))
+        );
+    }
+
+    #[kernel_test]
+    fn test_line_control() {
+        assert_eq!(Config::new().line_control(), 0x03);
+
+        let config = Config::new()
+            .data_bits(DataBits::Seven)
+            .parity(Parity::Even)
+            .stop_bits(StopBits::Two);
+        assert_eq!(config.line_control(), 0x1e);
+    }
+}
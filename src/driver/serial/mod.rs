@@ -0,0 +1,338 @@
+mod config;
+mod uart;
+
+pub use config::{Config, DataBits, Parity, StopBits};
+
+use crate::libs::buffer::RingBuffer;
+use crate::nucleus::debug;
+use crate::nucleus::interrupt::pic;
+use crate::nucleus::sync::{IrqMutex, Reentrant};
+use x86_64::structures::idt::InterruptStackFrame;
+
+const INPUT_SIZE: usize = 256;
+
+pub const CONSOLE: ComPort = ComPort::Com1;
+
+static PORTS: [IrqMutex<Option<SerialPort>>; 4] = [
+    IrqMutex::new(None),
+    IrqMutex::new(None),
+    IrqMutex::new(None),
+    IrqMutex::new(None),
+];
+
+pub type BreakHook = fn(ComPort, u8) -> bool;
+
+static BREAK_HOOK: spin::Mutex<Option<BreakHook>> = spin::Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComPort {
+    Com1,
+    Com2,
+    Com3,
+    Com4,
+}
+
+impl ComPort {
+    pub const ALL: [ComPort; 4] = [Self::Com1, Self::Com2, Self::Com3, Self::Com4];
+
+    pub const fn base(self) -> u16 {
+        match self {
+            Self::Com1 => 0x3f8,
+            Self::Com2 => 0x2f8,
+            Self::Com3 => 0x3e8,
+            Self::Com4 => 0x2e8,
+        }
+    }
+
+    pub const fn irq(self) -> u8 {
+        match self {
+            Self::Com1 | Self::Com3 => 4,
+            Self::Com2 | Self::Com4 => 3,
+        }
+    }
+
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SerialError {
+    NotPresent,
+    NotInitialized,
+    InvalidBaudRate(u32),
+    LoopbackFailed,
+}
+
+pub struct SerialPort {
+    uart: uart::Uart,
+    config: Config,
+    input: RingBuffer<u8, INPUT_SIZE>,
+    dropped: usize,
+}
+
+impl SerialPort {
+    pub fn config(&self) -> Config {
+        self.config
+    }
+
+    /// Number of received bytes lost because the input buffer was full.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.uart.write(bytes);
+    }
+
+    pub fn read_byte(&mut self) -> Option<u8> {
+        self.receive(|_| false);
+        self.input.pop()
+    }
+
+    /// Buffers received bytes except those `intercept` claims, and returns whether it claimed any.
+    fn receive<F>(&mut self, mut intercept: F) -> bool
+    where
+        F: FnMut(u8) -> bool,
+    {
+        let mut intercepted = false;
+
+        while let Some(byte) = self.uart.try_read() {
+            if intercept(byte) {
+                intercepted = true;
+            } else if self.input.push(byte).is_err() {
+                self.dropped += 1;
+            }
+        }
+
+        intercepted
+    }
+}
+
+impl core::fmt::Write for SerialPort {
+    fn write_str(&mut self, string: &str) -> core::fmt::Result {
+        self.write_bytes(string.as_bytes());
+        Ok(())
+    }
+}
+
+pub fn detect(port: ComPort) -> bool {
+    uart::Uart::new(port.base()).is_present()
+}
+
+pub fn init(port: ComPort, config: Config) -> Result<(), SerialError> {
+    let mut uart = uart::Uart::new(port.base());
+
+    if !uart.is_present() {
+        return Err(SerialError::NotPresent);
+    }
+
+    uart.configure(&config)?;
+
+    let serial_port = SerialPort {
+        uart,
+        config,
+        input: RingBuffer::new(),
+        dropped: 0,
+    };
+
+    *PORTS[port.index()].lock() = Some(serial_port);
+    Ok(())
+}
+
+pub fn enable_receive_interrupts(port: ComPort) -> Result<(), SerialError> {
+    with_port(port, |serial_port| {
+        serial_port.uart.set_receive_interrupt(true)
+    })
+    .ok_or(SerialError::NotInitialized)?;
+
+    pic::enable(port.irq());
+    Ok(())
+}
+
+pub fn with_port<F, R>(port: ComPort, function: F) -> Option<R>
+where
+    F: FnOnce(&mut SerialPort) -> R,
+{
+    PORTS[port.index()].lock().as_mut().map(function)
+}
+
+/// Polls the UART directly when called from a handler that interrupted a user of the same port,
+/// so a debugger stopped inside a serial write can still read its commands.
+pub fn read_byte(port: ComPort) -> Option<u8> {
+    match PORTS[port.index()].lock_checked() {
+        Ok(mut serial_port) => serial_port.as_mut().and_then(SerialPort::read_byte),
+        Err(Reentrant) => uart::Uart::new(port.base()).try_read(),
+    }
+}
+
+/// Falls back to the UART directly on re-entrance, like `write_fmt`.
+pub fn write_bytes(port: ComPort, bytes: &[u8]) -> Result<(), SerialError> {
+    match PORTS[port.index()].lock_checked() {
+        Ok(mut serial_port) => {
+            let serial_port = serial_port.as_mut().ok_or(SerialError::NotInitialized)?;
+            serial_port.write_bytes(bytes);
+        }
+        Err(Reentrant) => uart::Uart::new(port.base()).write(bytes),
+    }
+
+    Ok(())
+}
+
+/// Writes to any initialised port. Falls back to the UART directly when called from a handler
+/// that interrupted a write to the same port.
+pub fn write_fmt(port: ComPort, args: core::fmt::Arguments) -> Result<(), SerialError> {
+    use core::fmt::Write;
+
+    match PORTS[port.index()].lock_checked() {
+        Ok(mut serial_port) => {
+            let serial_port = serial_port.as_mut().ok_or(SerialError::NotInitialized)?;
+            serial_port.write_fmt(args).ok();
+        }
+        Err(Reentrant) => {
+            uart::Uart::new(port.base()).write_fmt(args).ok();
+        }
+    }
+
+    Ok(())
+}
+
+/// Called from the receive interrupt with every byte. Returning `true` drops the byte and raises
+/// a debug exception as soon as the interrupted code resumes, with that code's registers.
+pub fn set_break_hook(hook: Option<BreakHook>) {
+    x86_64::instructions::interrupts::without_interrupts(|| *BREAK_HOOK.lock() = hook);
+}
+
+/// Releases a port lock held by code that will never resume, such as a test that panicked while
+/// printing.
+///
+/// # Safety
+///
+/// Nothing may still be using the port.
+pub unsafe fn force_unlock(port: ComPort) {
+    PORTS[port.index()].force_unlock();
+}
+
+#[macro_export]
+macro_rules! serial_print {
+    ($($arg:tt)*) => ($crate::driver::serial::_print(format_args!($($arg)*)));
+}
+
+#[macro_export]
+macro_rules! serial_println {
+    () => ($crate::serial_print!("\n"));
+    ($($arg:tt)*) => ($crate::serial_print!("{}\n", format_args!($($arg)*)));
+}
+
+/// Falls back to [`_emergency_print`] when called from a handler that interrupted a print.
+pub fn _print(args: core::fmt::Arguments) {
+    use core::fmt::Write;
+
+    let mut console = match PORTS[CONSOLE.index()].lock_checked() {
+        Ok(console) => console,
+        Err(Reentrant) => return _emergency_print(args),
+    };
+
+    if console.is_none() {
+        drop(console);
+        init(CONSOLE, Config::new()).ok();
+        console = PORTS[CONSOLE.index()].lock();
+    }
+
+    if let Some(console) = console.as_mut() {
+        console.write_fmt(args).ok();
+    }
+}
+
+/// Writes to the console UART without taking the port lock, so it can never block. The output
+/// may interleave with a print it interrupted.
+pub fn _emergency_print(args: core::fmt::Arguments) {
+    use core::fmt::Write;
+
+    uart::Uart::new(CONSOLE.base()).write_fmt(args).ok();
+}
+
+pub(crate) extern "x86-interrupt" fn com1_com3_interrupt_handler(
+    mut stack_frame: InterruptStackFrame,
+) {
+    if handle_interrupt(ComPort::Com1.irq()) {
+        debug::trap_on_return(&mut stack_frame);
+    }
+}
+
+pub(crate) extern "x86-interrupt" fn com2_com4_interrupt_handler(
+    mut stack_frame: InterruptStackFrame,
+) {
+    if handle_interrupt(ComPort::Com2.irq()) {
+        debug::trap_on_return(&mut stack_frame);
+    }
+}
+
+/// Returns whether the break hook asked for a debug trap.
+fn handle_interrupt(irq: u8) -> bool {
+    let hook = BREAK_HOOK.try_lock().and_then(|hook| *hook);
+    let mut requested = false;
+
+    for port in ComPort::ALL.iter().filter(|port| port.irq() == irq) {
+        if let Some(mut serial_port) = PORTS[port.index()].try_lock() {
+            if let Some(serial_port) = serial_port.as_mut() {
+                requested |= serial_port.receive(|byte| hook.is_some_and(|hook| hook(*port, byte)));
+            }
+        }
+    }
+
+    pic::end_of_interrupt(irq);
+    requested
+}
+
+#[cfg(all(test, target_os = "none"))]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_detect() {
+        assert!(detect(ComPort::Com1));
+        assert_eq!(
+            init(ComPort::Com4, Config::new()),
+            Err(SerialError::NotPresent)
+        );
+        assert_eq!(read_byte(ComPort::Com4), None);
+    }
+
+    #[test_case]
+    fn test_reconfigure_console() {
+        let config = Config::new().baud_rate(38_400);
+
+        assert_eq!(init(CONSOLE, config), Ok(()));
+        assert_eq!(with_port(CONSOLE, |port| port.config()), Some(config));
+        crate::serial_println!("reconfigured console");
+
+        assert_eq!(init(CONSOLE, Config::new()), Ok(()));
+    }
+
+    #[test_case]
+    fn test_print_while_locked() {
+        with_port(CONSOLE, |_| {
+            crate::serial_println!("printed without the port lock")
+        });
+    }
+
+    #[test_case]
+    fn test_read_and_write_while_locked() {
+        with_port(CONSOLE, |_| {
+            read_byte(CONSOLE);
+            assert_eq!(
+                write_bytes(CONSOLE, b"written without the port lock\n"),
+                Ok(())
+            );
+        });
+    }
+
+    #[test_case]
+    fn test_invalid_baud_rate() {
+        assert_eq!(
+            init(CONSOLE, Config::new().baud_rate(1_000)),
+            Err(SerialError::InvalidBaudRate(1_000))
+        );
+    }
+}
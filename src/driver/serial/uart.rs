@@ -0,0 +1,108 @@
+use super::config::Config;
+use super::SerialError;
+use x86_64::instructions::port::Port;
+
+const DATA: u16 = 0;
+const INTERRUPT_ENABLE: u16 = 1;
+const FIFO_CONTROL: u16 = 2;
+const LINE_CONTROL: u16 = 3;
+const MODEM_CONTROL: u16 = 4;
+const LINE_STATUS: u16 = 5;
+const SCRATCH: u16 = 7;
+
+const DIVISOR_LATCH: u8 = 0x80;
+const RECEIVE_INTERRUPT: u8 = 0x01;
+const FIFO_SETUP: u8 = 0xc7;
+const DATA_TERMINAL_READY: u8 = 0x01;
+const REQUEST_TO_SEND: u8 = 0x02;
+const INTERRUPT_LINE: u8 = 0x08;
+const LOOPBACK: u8 = 0x10;
+const DATA_READY: u8 = 0x01;
+const TRANSMIT_EMPTY: u8 = 0x20;
+
+const FIFO_SIZE: usize = 16;
+const TEST_BYTE: u8 = 0xae;
+
+/// A 16550-compatible UART at an I/O port base.
+pub(super) struct Uart {
+    base: u16,
+}
+
+impl Uart {
+    pub(super) const fn new(base: u16) -> Self {
+        Self { base }
+    }
+
+    pub(super) fn is_present(&self) -> bool {
+        self.write_register(SCRATCH, TEST_BYTE);
+        self.read_register(SCRATCH) == TEST_BYTE
+    }
+
+    pub(super) fn configure(&mut self, config: &Config) -> Result<(), SerialError> {
+        let divisor = config.divisor()?;
+
+        self.write_register(INTERRUPT_ENABLE, 0);
+        self.write_register(LINE_CONTROL, DIVISOR_LATCH);
+        self.write_register(DATA, divisor as u8);
+        self.write_register(INTERRUPT_ENABLE, (divisor >> 8) as u8);
+        self.write_register(LINE_CONTROL, config.line_control());
+        self.write_register(FIFO_CONTROL, FIFO_SETUP);
+
+        let modem_control = DATA_TERMINAL_READY | REQUEST_TO_SEND | INTERRUPT_LINE;
+
+        self.write_register(MODEM_CONTROL, modem_control | LOOPBACK);
+        self.write_register(DATA, TEST_BYTE);
+        let echoed = self.try_read();
+        self.write_register(MODEM_CONTROL, modem_control);
+
+        match echoed {
+            Some(TEST_BYTE) => Ok(()),
+            _ => Err(SerialError::LoopbackFailed),
+        }
+    }
+
+    pub(super) fn set_receive_interrupt(&mut self, enabled: bool) {
+        let value = match enabled {
+            true => RECEIVE_INTERRUPT,
+            false => 0,
+        };
+
+        self.write_register(INTERRUPT_ENABLE, value);
+    }
+
+    pub(super) fn try_read(&mut self) -> Option<u8> {
+        match self.read_register(LINE_STATUS) & DATA_READY {
+            0 => None,
+            _ => Some(self.read_register(DATA)),
+        }
+    }
+
+    /// Writes whole FIFO-sized chunks each time the transmitter drains, rather than waiting for
+    /// every byte.
+    pub(super) fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(FIFO_SIZE) {
+            while self.read_register(LINE_STATUS) & TRANSMIT_EMPTY == 0 {
+                core::hint::spin_loop();
+            }
+
+            for byte in chunk {
+                self.write_register(DATA, *byte);
+            }
+        }
+    }
+
+    fn read_register(&self, offset: u16) -> u8 {
+        unsafe { Port::<u8>::new(self.base + offset).read() }
+    }
+
+    fn write_register(&self, offset: u16, value: u8) {
+        unsafe { Port::<u8>::new(self.base + offset).write(value) }
+    }
+}
+
+impl core::fmt::Write for Uart {
+    fn write_str(&mut self, string: &str) -> core::fmt::Result {
+        self.write(string.as_bytes());
+        Ok(())
+    }
+}
@@ -1,30 +1,86 @@
 use super::character;
+use crate::libs::buffer::{GridBuffer, Region};
 
-pub(super) const VGA_BUFFER_WIDTH: usize = 80;
-pub(super) const VGA_BUFFER_HEIGHT: usize = 25;
+pub(super) const VGA_BUFFER_WIDTH: usize = 90;
+pub(super) const VGA_BUFFER_HEIGHT: usize = 60;
+
+pub(super) const VGA_MEMORY_ADDRESS: usize = 0xb8000;
+
+type VideoMemory = [u16; VGA_BUFFER_WIDTH * VGA_BUFFER_HEIGHT];
 
 pub(crate) struct VGABuffer {
-    buffer: crate::libs::buffer::GridBuffer<u16, VGA_BUFFER_WIDTH, VGA_BUFFER_HEIGHT>,
+    buffer: GridBuffer<u16, VGA_BUFFER_WIDTH, VGA_BUFFER_HEIGHT>,
+    width: usize,
+    height: usize,
+    memory: Option<&'static mut VideoMemory>,
 }
 
 impl VGABuffer {
-    pub const WIDTH: usize = VGA_BUFFER_WIDTH;
-    pub const HEIGHT: usize = VGA_BUFFER_HEIGHT;
+    pub fn new() -> VGABuffer {
+        VGABuffer {
+            buffer: GridBuffer::new(),
+            width: 80,
+            height: 25,
+            memory: Some(unsafe { &mut *(VGA_MEMORY_ADDRESS as *mut VideoMemory) }),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
 
-    pub fn get() -> &'static mut VGABuffer {
-        unsafe { &mut *(0xb8000 as *mut VGABuffer) }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn region(&self) -> Region {
+        Region::full(self.height, self.width)
+    }
+
+    pub fn resize(&mut self, width: usize, height: usize) {
+        self.width = core::cmp::min(width, VGA_BUFFER_WIDTH);
+        self.height = core::cmp::min(height, VGA_BUFFER_HEIGHT);
+        self.buffer.clear();
+        self.sync();
     }
 
     pub fn set(&mut self, position: (usize, usize), char: character::Character) {
-        self.buffer.content[position.0][position.1] = char.into();
+        let value = char.into();
+        self.buffer.content[position.0][position.1] = value;
+        self.write_through(position, value);
+    }
+
+    pub fn get(&self, position: (usize, usize)) -> u16 {
+        self.buffer.content[position.0][position.1]
     }
 
     pub fn move_up(&mut self, count: usize) {
-        self.buffer.shift((0, -(count as isize)));
+        self.buffer
+            .shift_region(self.region(), (0, -(count as isize)));
+        self.sync();
     }
 
     pub fn clear(&mut self) {
         self.buffer.clear();
+        self.sync();
+    }
+
+    pub fn draw<const WIDTH: usize, const HEIGHT: usize>(
+        &mut self,
+        source: &GridBuffer<u16, WIDTH, HEIGHT>,
+    ) {
+        let region = source.region().intersect(&self.region());
+        self.buffer.blit(source, region, (0, 0));
+        self.sync();
+    }
+
+    pub fn sync(&mut self) {
+        for line_pos in 0..self.height {
+            for char_pos in 0..self.width {
+                let value = self.buffer.content[line_pos][char_pos];
+                self.write_through((line_pos, char_pos), value);
+            }
+        }
     }
 
     pub fn get_text(&self) -> [[u8; VGA_BUFFER_WIDTH]; VGA_BUFFER_HEIGHT] {
@@ -40,6 +96,14 @@ impl VGABuffer {
 
         text
     }
+
+    fn write_through(&mut self, position: (usize, usize), value: u16) {
+        let index = position.0 * self.width + position.1;
+
+        if let Some(memory) = self.memory.as_mut() {
+            unsafe { core::ptr::write_volatile(&mut memory[index], value) };
+        }
+    }
 }
 
 #[cfg(test)]
@@ -48,9 +112,10 @@ pub mod testing {
 
     pub fn construct_buffer_from_strings(lines: &[&str]) -> VGABuffer {
         let mut buffer = VGABuffer {
-            buffer: crate::libs::buffer::GridBuffer::new_with_default(
-                character::Character::default().into(),
-            ),
+            buffer: GridBuffer::new_with_default(character::Character::default().into()),
+            width: VGA_BUFFER_WIDTH,
+            height: VGA_BUFFER_HEIGHT,
+            memory: None,
         };
 
         for (line_pos, line) in lines.iter().enumerate() {
@@ -66,8 +131,9 @@ pub mod testing {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::libs::testing::kernel_test;
 
-    #[test_case]
+    #[kernel_test]
     fn test_construct_buffer_from_strings() {
         let lines = ["abc", "defgh", ""];
         let buffer = testing::construct_buffer_from_strings(&lines);
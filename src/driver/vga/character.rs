@@ -22,6 +22,13 @@ impl Character {
             attribute: style.as_byte(),
         }
     }
+
+    pub(super) const fn glyph(codepoint: u8, style: attribute::Attribute) -> Self {
+        Self {
+            codepoint,
+            attribute: style.as_byte(),
+        }
+    }
 }
 
 impl Default for Character {
@@ -62,3 +69,49 @@ impl From<u8> for Character {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_new_replaces_unprintable() {
+        let style = attribute::Attribute::default();
+
+        assert_eq!(Character::new(b'a', style).codepoint, b'a');
+        assert_eq!(Character::new(b'\n', style).codepoint, b'\n');
+        assert_eq!(
+            Character::new(0x07, style).codepoint,
+            UNPRINTABLE_CHAR_VALUE
+        );
+        assert_eq!(
+            Character::new(0x80, style).codepoint,
+            UNPRINTABLE_CHAR_VALUE
+        );
+    }
+
+    #[kernel_test]
+    fn test_u16_round_trip() {
+        let character = Character {
+            codepoint: b'x',
+            attribute: 0x4e,
+        };
+        let value: u16 = character.into();
+
+        assert_eq!(value, 0x4e78);
+        assert_eq!(Character::from(value), character);
+    }
+
+    #[kernel_test]
+    fn test_from_u8() {
+        let character = Character::from(0x01u8);
+
+        assert_eq!(character.codepoint, 0x01);
+        assert_eq!(
+            character,
+            Character::glyph(0x01, attribute::Attribute::default())
+        );
+        assert_eq!(Character::default().attribute, 0x0f);
+    }
+}
@@ -25,4 +25,22 @@ impl Color {
             Color::Bright(color_name) => (*color_name as u8) | 8,
         }
     }
+
+    pub const fn name(self) -> ColorName {
+        match self {
+            Color::Dim(color_name) | Color::Bright(color_name) => color_name,
+        }
+    }
+
+    pub const fn is_bright(self) -> bool {
+        matches!(self, Color::Bright(_))
+    }
+
+    pub const fn bright(self) -> Color {
+        Color::Bright(self.name())
+    }
+
+    pub const fn dim(self) -> Color {
+        Color::Dim(self.name())
+    }
 }
@@ -0,0 +1,102 @@
+use super::registers;
+
+pub const GLYPH_COUNT: usize = 256;
+pub const MAX_GLYPH_HEIGHT: usize = 32;
+
+#[derive(Clone)]
+pub struct Font {
+    height: usize,
+    glyphs: [[u8; MAX_GLYPH_HEIGHT]; GLYPH_COUNT],
+}
+
+impl Font {
+    pub const fn empty(height: usize) -> Self {
+        let height = match height > MAX_GLYPH_HEIGHT {
+            true => MAX_GLYPH_HEIGHT,
+            false => height,
+        };
+
+        Self {
+            height,
+            glyphs: [[0; MAX_GLYPH_HEIGHT]; GLYPH_COUNT],
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn glyph(&self, codepoint: u8) -> &[u8] {
+        &self.glyphs[codepoint as usize][..self.height]
+    }
+
+    pub fn set_glyph(&mut self, codepoint: u8, rows: &[u8]) {
+        let glyph = &mut self.glyphs[codepoint as usize];
+        let count = core::cmp::min(rows.len(), self.height);
+
+        glyph.fill(0);
+        glyph[..count].copy_from_slice(&rows[..count]);
+    }
+
+    pub fn scaled(&self, height: usize) -> Font {
+        let mut font = Font::empty(height);
+
+        for (codepoint, glyph) in self.glyphs.iter().enumerate() {
+            for row in 0..font.height {
+                font.glyphs[codepoint][row] = glyph[row * self.height / font.height];
+            }
+        }
+
+        font
+    }
+
+    pub(super) fn read(height: usize) -> Font {
+        let mut font = Font::empty(height);
+
+        registers::with_font_plane(|plane| {
+            for (codepoint, glyph) in font.glyphs.iter_mut().enumerate() {
+                for (row, value) in glyph.iter_mut().enumerate() {
+                    let offset = codepoint * MAX_GLYPH_HEIGHT + row;
+                    *value = unsafe { core::ptr::read_volatile(&plane[offset]) };
+                }
+            }
+        });
+
+        font
+    }
+
+    pub(super) fn write(&self) {
+        registers::with_font_plane(|plane| {
+            for (codepoint, glyph) in self.glyphs.iter().enumerate() {
+                for (row, value) in glyph.iter().enumerate() {
+                    let offset = codepoint * MAX_GLYPH_HEIGHT + row;
+                    unsafe { core::ptr::write_volatile(&mut plane[offset], *value) };
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_set_glyph() {
+        let mut font = Font::empty(4);
+        font.set_glyph(b'a', &[1, 2, 3, 4, 5, 6]);
+
+        assert_eq!(font.glyph(b'a'), &[1, 2, 3, 4]);
+        assert_eq!(font.glyph(b'b'), &[0, 0, 0, 0]);
+    }
+
+    #[kernel_test]
+    fn test_scaled() {
+        let mut font = Font::empty(4);
+        font.set_glyph(b'a', &[1, 2, 3, 4]);
+
+        assert_eq!(font.scaled(2).glyph(b'a'), &[1, 3]);
+        assert_eq!(font.scaled(8).glyph(b'a'), &[1, 1, 2, 2, 3, 3, 4, 4]);
+    }
+}
@@ -0,0 +1,190 @@
+use super::font;
+
+pub(super) const FRAMEBUFFER_ADDRESS: usize = 0xa0000;
+
+pub struct Framebuffer<'a> {
+    pixels: &'a mut [u8],
+    width: usize,
+    height: usize,
+}
+
+impl<'a> Framebuffer<'a> {
+    pub fn new(pixels: &'a mut [u8], width: usize, height: usize) -> Self {
+        assert!(pixels.len() >= width * height);
+
+        Self {
+            pixels,
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn clear(&mut self, color: u8) {
+        self.fill_rectangle((0, 0), (self.width, self.height), color);
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Option<u8> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        Some(unsafe { core::ptr::read_volatile(&self.pixels[y * self.width + x]) })
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: u8) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+
+        unsafe { core::ptr::write_volatile(&mut self.pixels[y * self.width + x], color) };
+    }
+
+    pub fn line(&mut self, from: (usize, usize), to: (usize, usize), color: u8) {
+        let (mut x, mut y) = (from.0 as isize, from.1 as isize);
+        let (end_x, end_y) = (to.0 as isize, to.1 as isize);
+
+        let delta_x = (end_x - x).abs();
+        let delta_y = -(end_y - y).abs();
+        let step_x = if x < end_x { 1 } else { -1 };
+        let step_y = if y < end_y { 1 } else { -1 };
+        let mut error = delta_x + delta_y;
+
+        loop {
+            self.set_pixel(x as usize, y as usize, color);
+
+            if x == end_x && y == end_y {
+                break;
+            }
+
+            let doubled = 2 * error;
+
+            if doubled >= delta_y {
+                error += delta_y;
+                x += step_x;
+            }
+
+            if doubled <= delta_x {
+                error += delta_x;
+                y += step_y;
+            }
+        }
+    }
+
+    pub fn rectangle(&mut self, position: (usize, usize), size: (usize, usize), color: u8) {
+        let (x, y) = position;
+        let (width, height) = size;
+
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let (right, bottom) = (x + width - 1, y + height - 1);
+
+        self.line((x, y), (right, y), color);
+        self.line((x, bottom), (right, bottom), color);
+        self.line((x, y), (x, bottom), color);
+        self.line((right, y), (right, bottom), color);
+    }
+
+    pub fn fill_rectangle(&mut self, position: (usize, usize), size: (usize, usize), color: u8) {
+        let (x, y) = position;
+        let right = core::cmp::min(x.saturating_add(size.0), self.width);
+        let bottom = core::cmp::min(y.saturating_add(size.1), self.height);
+
+        for pixel_y in y..bottom {
+            for pixel_x in x..right {
+                self.set_pixel(pixel_x, pixel_y, color);
+            }
+        }
+    }
+
+    pub fn draw_char(
+        &mut self,
+        position: (usize, usize),
+        codepoint: u8,
+        font: &font::Font,
+        foreground: u8,
+        background: Option<u8>,
+    ) {
+        let (x, y) = position;
+
+        for (row, bits) in font.glyph(codepoint).iter().enumerate() {
+            for column in 0..8 {
+                let set = bits & (0x80 >> column) != 0;
+
+                match (set, background) {
+                    (true, _) => self.set_pixel(x + column, y + row, foreground),
+                    (false, Some(background)) => self.set_pixel(x + column, y + row, background),
+                    (false, None) => {}
+                }
+            }
+        }
+    }
+
+    pub fn draw_str(
+        &mut self,
+        position: (usize, usize),
+        string: &str,
+        font: &font::Font,
+        foreground: u8,
+        background: Option<u8>,
+    ) {
+        let (x, y) = position;
+
+        for (index, byte) in string.bytes().enumerate() {
+            self.draw_char((x + index * 8, y), byte, font, foreground, background);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_line() {
+        let mut pixels = [0u8; 16];
+        let mut framebuffer = Framebuffer::new(&mut pixels, 4, 4);
+
+        framebuffer.line((0, 0), (3, 3), 1);
+        framebuffer.line((3, 0), (0, 0), 2);
+
+        assert_eq!(pixels, [2, 2, 2, 2, 0, 1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1]);
+    }
+
+    #[kernel_test]
+    fn test_rectangle() {
+        let mut pixels = [0u8; 16];
+        let mut framebuffer = Framebuffer::new(&mut pixels, 4, 4);
+
+        framebuffer.rectangle((0, 0), (4, 3), 1);
+        framebuffer.fill_rectangle((1, 1), (2, 5), 2);
+
+        assert_eq!(pixels, [1, 1, 1, 1, 1, 2, 2, 1, 1, 2, 2, 1, 0, 2, 2, 0]);
+    }
+
+    #[kernel_test]
+    fn test_draw_char() {
+        let mut font = font::Font::empty(2);
+        font.set_glyph(b'a', &[0b1000_0001, 0b0100_0000]);
+
+        let mut pixels = [0u8; 20];
+        let mut framebuffer = Framebuffer::new(&mut pixels, 10, 2);
+
+        framebuffer.draw_char((1, 0), b'a', &font, 7, Some(3));
+
+        assert_eq!(
+            pixels,
+            [0, 7, 3, 3, 3, 3, 3, 3, 7, 0, 0, 3, 7, 3, 3, 3, 3, 3, 3, 0]
+        );
+    }
+}
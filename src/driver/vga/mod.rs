@@ -2,12 +2,30 @@ mod attribute;
 mod buffer;
 mod character;
 mod color;
+mod font;
+mod mode;
+mod palette;
+mod psf;
+mod registers;
+mod snapshot;
+mod style;
 mod writer;
 
+pub mod graphics;
+pub mod tui;
+
 pub use color::{Color, ColorName};
+pub use font::Font;
+pub use mode::Mode;
+pub use palette::{Palette, Rgb};
+pub use psf::PsfError;
+pub use snapshot::{Diff, Snapshot};
+pub use style::{BackgroundMode, Style};
+
+use crate::nucleus::sync::{IrqMutex, Reentrant};
 
 lazy_static::lazy_static! {
-    pub static ref VGA_WRITER: spin::Mutex<writer::Writer> = spin::Mutex::new(writer::Writer::new());
+    pub static ref VGA_WRITER: IrqMutex<writer::Writer> = IrqMutex::new(writer::Writer::new());
 }
 
 #[macro_export]
@@ -21,12 +39,155 @@ macro_rules! println {
     ($($arg:tt)*) => ($crate::print!("{}\n", format_args!($($arg)*)));
 }
 
+/// Logs to the kernel message ring and prints to the screen.
 pub fn _print(args: core::fmt::Arguments) {
+    crate::libs::dmesg::print(args);
+    print_screen(args);
+}
+
+/// Prints to the screen only, for output that is not a kernel message. Prints to serial instead
+/// when called from a handler that interrupted a print, since the interrupted writer is
+/// mid-update.
+pub fn print_screen(args: core::fmt::Arguments) {
+    use core::fmt::Write;
+
+    match VGA_WRITER.lock_checked() {
+        Ok(mut writer) => writer.write_fmt(args).unwrap(),
+        Err(Reentrant) => crate::driver::serial::_emergency_print(args),
+    }
+}
+
+/// Takes the writer over if the interrupted code held it. Only for handlers that never return to
+/// that code.
+pub fn _emergency_print(args: core::fmt::Arguments) {
     use core::fmt::Write;
-    VGA_WRITER.lock().write_fmt(args).unwrap();
+
+    let mut writer = match VGA_WRITER.lock_checked() {
+        Ok(writer) => writer,
+        Err(Reentrant) => {
+            unsafe { VGA_WRITER.force_unlock() };
+            VGA_WRITER.lock()
+        }
+    };
+
+    writer.write_fmt(args).ok();
+}
+
+/// Restores the previous style when dropped. Does nothing if the style could not be applied.
+pub struct StyleGuard {
+    previous: Option<attribute::Attribute>,
+}
+
+impl Drop for StyleGuard {
+    fn drop(&mut self) {
+        if let (Some(previous), Ok(mut writer)) = (self.previous, VGA_WRITER.lock_checked()) {
+            writer.restore_style(previous);
+        }
+    }
+}
+
+/// Leaves the style alone when called from a handler that interrupted a print, whose output goes
+/// to serial anyway.
+pub fn push_style(style: Style) -> StyleGuard {
+    StyleGuard {
+        previous: VGA_WRITER
+            .lock_checked()
+            .ok()
+            .map(|mut writer| writer.replace_style(style)),
+    }
+}
+
+pub fn with_style<F, R>(style: Style, function: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let _guard = push_style(style);
+    function()
+}
+
+pub fn set_background_mode(background_mode: BackgroundMode) {
+    mode::set_background_mode(background_mode);
+}
+
+pub fn background_mode() -> BackgroundMode {
+    mode::background_mode()
+}
+
+/// Fails when called from a handler that interrupted a print, since the writer is mid-update.
+pub fn set_mode(mode: Mode) -> Result<(), Reentrant> {
+    let mut writer = VGA_WRITER.lock_checked()?;
+    mode::switch(mode);
+
+    if mode.is_text() {
+        let (width, height) = mode.dimensions();
+        writer.resize(width, height);
+    }
+
+    Ok(())
+}
+
+pub fn mode() -> Mode {
+    mode::current()
+}
+
+pub fn system_font() -> Option<Font> {
+    mode::system_font()
+}
+
+pub fn set_font(font: Font) {
+    mode::set_font(Some(font));
+}
+
+pub fn reset_font() {
+    mode::set_font(None);
+}
+
+pub fn set_glyph(codepoint: u8, rows: &[u8]) {
+    mode::update_font(|font| font.set_glyph(codepoint, rows));
+}
+
+pub fn set_palette(palette: &Palette) {
+    let mode = mode::current();
+
+    for (color, value) in palette.colors() {
+        palette::write(mode.dac_index(color), value);
+    }
+}
+
+pub fn set_palette_color(color: Color, value: Rgb) {
+    palette::write(mode::current().dac_index(color.as_byte()), value);
+}
+
+pub fn palette_color(color: Color) -> Rgb {
+    palette::read(mode::current().dac_index(color.as_byte()))
+}
+
+/// Fails when called from a handler that interrupted a print, since the screen is mid-update.
+pub fn snapshot() -> Result<Snapshot, Reentrant> {
+    Ok(Snapshot::capture(VGA_WRITER.lock_checked()?.buffer()))
+}
+
+/// The pixels of the current graphics mode, or `None` in a text mode.
+///
+/// # Safety
+///
+/// The framebuffer must be dropped before the next `set_mode`, since the memory becomes font and
+/// text memory in text modes. Only one framebuffer may be alive at a time.
+pub unsafe fn framebuffer() -> Option<graphics::Framebuffer<'static>> {
+    let mode = mode::current();
+
+    if mode.is_text() {
+        return None;
+    }
+
+    let (width, height) = mode.dimensions();
+    let pixels =
+        core::slice::from_raw_parts_mut(graphics::FRAMEBUFFER_ADDRESS as *mut u8, width * height);
+
+    Some(graphics::Framebuffer::new(pixels, width, height))
 }
 
-#[cfg(test)]
+#[cfg(all(test, target_os = "none"))]
 mod tests {
     use super::*;
 
@@ -42,4 +203,135 @@ mod tests {
 
         VGA_WRITER.lock().assert_buffer_text_eq(expected_buffer);
     }
+
+    #[test_case]
+    fn test_println_while_locked() {
+        let mut writer = VGA_WRITER.lock();
+        writer.clear();
+
+        println!("Printed to serial instead");
+
+        let expected_buffer = buffer::testing::construct_buffer_from_strings(&[]);
+        writer.assert_buffer_text_eq(expected_buffer);
+    }
+
+    #[test_case]
+    fn test_with_style_while_locked() {
+        let mut writer = VGA_WRITER.lock();
+        writer.clear();
+
+        let style = Style::new().foreground(Color::Bright(ColorName::Red));
+        let result = with_style(style, || {
+            println!("Styled and printed to serial instead");
+            42
+        });
+
+        assert_eq!(result, 42);
+        assert_eq!(set_mode(Mode::Text80x50), Err(Reentrant));
+        assert!(snapshot().is_err());
+
+        let expected_buffer = buffer::testing::construct_buffer_from_strings(&[]);
+        writer.assert_buffer_text_eq(expected_buffer);
+    }
+
+    #[test_case]
+    fn test_set_text_mode() {
+        set_mode(Mode::Text80x50).unwrap();
+        VGA_WRITER.lock().clear();
+
+        for line in 0..50 {
+            print!("\n{}", line);
+        }
+
+        assert_eq!(VGA_WRITER.lock().dimensions(), (80, 50));
+
+        let memory = buffer::VGA_MEMORY_ADDRESS as *const u16;
+        let first = unsafe { core::ptr::read_volatile(memory) } as u8;
+        let last = unsafe { core::ptr::read_volatile(memory.add(49 * 80 + 1)) } as u8;
+
+        assert_eq!(first, b'0');
+        assert_eq!(last, b'9');
+
+        set_mode(Mode::Text80x25).unwrap();
+        assert_eq!(VGA_WRITER.lock().dimensions(), (80, 25));
+    }
+
+    #[test_case]
+    fn test_graphics_mode() {
+        set_mode(Mode::Graphics320x200).unwrap();
+
+        let mut framebuffer = unsafe { framebuffer() }.unwrap();
+        framebuffer.clear(0);
+        framebuffer.line((10, 10), (20, 20), 4);
+
+        let memory = graphics::FRAMEBUFFER_ADDRESS as *const u8;
+        let pixel = unsafe { core::ptr::read_volatile(memory.add(15 * 320 + 15)) };
+
+        assert_eq!(pixel, 4);
+        assert_eq!(framebuffer.pixel(16, 15), Some(0));
+
+        set_mode(Mode::Text80x25).unwrap();
+        assert!(unsafe { super::framebuffer() }.is_none());
+    }
+
+    #[test_case]
+    fn test_set_palette_color() {
+        let color = Color::Bright(ColorName::Blue);
+        let previous = palette_color(color);
+
+        set_palette_color(color, Rgb::from_hex(0x123456));
+        assert_eq!(palette_color(color), Rgb::from_hex(0x103455));
+
+        set_palette_color(color, previous);
+    }
+
+    #[test_case]
+    fn test_set_glyph() {
+        set_glyph(0x01, &[0xff; 16]);
+
+        let mut rows = [0u8; 16];
+        registers::with_font_plane(|plane| rows.copy_from_slice(&plane[32..48]));
+        assert_eq!(rows, [0xff; 16]);
+
+        reset_font();
+    }
+
+    #[test_case]
+    fn test_with_style_print() {
+        VGA_WRITER.lock().clear();
+
+        let style = Style::new().foreground(Color::Bright(ColorName::Red));
+        with_style(style, || print!("a"));
+        print!("b");
+
+        let memory = buffer::VGA_MEMORY_ADDRESS as *const u16;
+        let first = unsafe { core::ptr::read_volatile(memory) };
+        let second = unsafe { core::ptr::read_volatile(memory.add(1)) };
+
+        assert_eq!(first, 0x0c00 | b'a' as u16);
+        assert_eq!(second, 0x0f00 | b'b' as u16);
+    }
+
+    #[test_case]
+    fn test_set_background_mode() {
+        set_background_mode(BackgroundMode::Bright);
+        assert_eq!(registers::read_attribute(0x10) & 0x08, 0);
+
+        set_background_mode(BackgroundMode::Blink);
+        assert_eq!(registers::read_attribute(0x10) & 0x08, 0x08);
+    }
+
+    #[test_case]
+    fn test_styled_text_snapshot() {
+        VGA_WRITER.lock().clear();
+
+        print!("Snapshot ");
+        with_style(
+            Style::new().foreground(Color::Bright(ColorName::Red)),
+            || print!("red"),
+        );
+        print!("\n\\ok");
+
+        crate::assert_snapshot!("styled_text");
+    }
 }
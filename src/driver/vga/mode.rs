@@ -0,0 +1,213 @@
+use super::font;
+use super::registers;
+use super::style;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Text80x25,
+    Text80x50,
+    Text90x60,
+    Graphics320x200,
+}
+
+static CURRENT_MODE: spin::Mutex<Mode> = spin::Mutex::new(Mode::Text80x25);
+static SYSTEM_FONT: spin::Mutex<Option<font::Font>> = spin::Mutex::new(None);
+static CUSTOM_FONT: spin::Mutex<Option<font::Font>> = spin::Mutex::new(None);
+static BACKGROUND_MODE: spin::Mutex<style::BackgroundMode> =
+    spin::Mutex::new(style::BackgroundMode::Blink);
+
+const ATTRIBUTE_MODE_CONTROL: u8 = 0x10;
+const ATTRIBUTE_BLINK_ENABLE: u8 = 0x08;
+
+impl Mode {
+    pub const fn dimensions(self) -> (usize, usize) {
+        match self {
+            Mode::Text80x25 => (80, 25),
+            Mode::Text80x50 => (80, 50),
+            Mode::Text90x60 => (90, 60),
+            Mode::Graphics320x200 => (320, 200),
+        }
+    }
+
+    pub const fn font_height(self) -> Option<usize> {
+        match self {
+            Mode::Text80x25 => Some(16),
+            Mode::Text80x50 | Mode::Text90x60 => Some(8),
+            Mode::Graphics320x200 => None,
+        }
+    }
+
+    pub const fn is_text(self) -> bool {
+        self.font_height().is_some()
+    }
+
+    pub(super) const fn dac_index(self, color: u8) -> u8 {
+        self.registers().attribute[(color & 0x0f) as usize]
+    }
+
+    const fn registers(self) -> &'static registers::RegisterSet {
+        match self {
+            Mode::Text80x25 => &TEXT_80X25,
+            Mode::Text80x50 => &TEXT_80X50,
+            Mode::Text90x60 => &TEXT_90X60,
+            Mode::Graphics320x200 => &GRAPHICS_320X200,
+        }
+    }
+}
+
+pub(super) fn current() -> Mode {
+    *CURRENT_MODE.lock()
+}
+
+pub(super) fn system_font() -> Option<font::Font> {
+    SYSTEM_FONT.lock().clone()
+}
+
+pub(super) fn switch(mode: Mode) {
+    let mut current = CURRENT_MODE.lock();
+
+    capture_system_font(*current);
+    registers::load(mode.registers());
+    upload_font(mode);
+
+    if mode.is_text() {
+        apply_background_mode(*BACKGROUND_MODE.lock());
+    }
+
+    *current = mode;
+}
+
+pub(super) fn background_mode() -> style::BackgroundMode {
+    *BACKGROUND_MODE.lock()
+}
+
+pub(super) fn set_background_mode(background_mode: style::BackgroundMode) {
+    let current = CURRENT_MODE.lock();
+    let mut stored = BACKGROUND_MODE.lock();
+
+    if current.is_text() {
+        apply_background_mode(background_mode);
+    }
+
+    *stored = background_mode;
+}
+
+pub(super) fn set_font(font: Option<font::Font>) {
+    let current = CURRENT_MODE.lock();
+
+    capture_system_font(*current);
+    *CUSTOM_FONT.lock() = font;
+    upload_font(*current);
+}
+
+pub(super) fn update_font<F>(function: F)
+where
+    F: FnOnce(&mut font::Font),
+{
+    let current = CURRENT_MODE.lock();
+    capture_system_font(*current);
+
+    {
+        let mut custom_font = CUSTOM_FONT.lock();
+
+        if custom_font.is_none() {
+            *custom_font = SYSTEM_FONT.lock().clone();
+        }
+
+        if let Some(font) = custom_font.as_mut() {
+            function(font);
+        }
+    }
+
+    upload_font(*current);
+}
+
+fn apply_background_mode(background_mode: style::BackgroundMode) {
+    let control = registers::read_attribute(ATTRIBUTE_MODE_CONTROL);
+
+    let control = match background_mode {
+        style::BackgroundMode::Blink => control | ATTRIBUTE_BLINK_ENABLE,
+        style::BackgroundMode::Bright => control & !ATTRIBUTE_BLINK_ENABLE,
+    };
+
+    registers::write_attribute(ATTRIBUTE_MODE_CONTROL, control);
+}
+
+fn capture_system_font(current: Mode) {
+    let mut system_font = SYSTEM_FONT.lock();
+
+    if let (None, Some(height)) = (system_font.as_ref(), current.font_height()) {
+        *system_font = Some(font::Font::read(height));
+    }
+}
+
+fn upload_font(mode: Mode) {
+    let height = match mode.font_height() {
+        Some(height) => height,
+        None => return,
+    };
+
+    let custom_font = CUSTOM_FONT.lock();
+    let system_font = SYSTEM_FONT.lock();
+
+    if let Some(font) = custom_font.as_ref().or(system_font.as_ref()) {
+        match font.height() == height {
+            true => font.write(),
+            false => font.scaled(height).write(),
+        }
+    }
+}
+
+const TEXT_ATTRIBUTE: [u8; 21] = [
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x14, 0x07, 0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f,
+    0x0c, 0x00, 0x0f, 0x08, 0x00,
+];
+
+const TEXT_GRAPHICS: [u8; 9] = [0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x0e, 0x00, 0xff];
+
+const TEXT_80X25: registers::RegisterSet = registers::RegisterSet {
+    misc_output: 0x67,
+    sequencer: [0x03, 0x00, 0x03, 0x00, 0x02],
+    crtc: [
+        0x5f, 0x4f, 0x50, 0x82, 0x55, 0x81, 0xbf, 0x1f, 0x00, 0x4f, 0x0d, 0x0e, 0x00, 0x00, 0x00,
+        0x00, 0x9c, 0x0e, 0x8f, 0x28, 0x1f, 0x96, 0xb9, 0xa3, 0xff,
+    ],
+    graphics: TEXT_GRAPHICS,
+    attribute: TEXT_ATTRIBUTE,
+};
+
+const TEXT_80X50: registers::RegisterSet = registers::RegisterSet {
+    misc_output: 0x67,
+    sequencer: [0x03, 0x00, 0x03, 0x00, 0x02],
+    crtc: [
+        0x5f, 0x4f, 0x50, 0x82, 0x55, 0x81, 0xbf, 0x1f, 0x00, 0x47, 0x06, 0x07, 0x00, 0x00, 0x00,
+        0x00, 0x9c, 0x8e, 0x8f, 0x28, 0x1f, 0x96, 0xb9, 0xa3, 0xff,
+    ],
+    graphics: TEXT_GRAPHICS,
+    attribute: TEXT_ATTRIBUTE,
+};
+
+const TEXT_90X60: registers::RegisterSet = registers::RegisterSet {
+    misc_output: 0xe7,
+    sequencer: [0x03, 0x01, 0x03, 0x00, 0x02],
+    crtc: [
+        0x6b, 0x59, 0x5a, 0x82, 0x60, 0x8d, 0x0b, 0x3e, 0x00, 0x47, 0x06, 0x07, 0x00, 0x00, 0x00,
+        0x00, 0xea, 0x0c, 0xdf, 0x2d, 0x08, 0xe8, 0x05, 0xa3, 0xff,
+    ],
+    graphics: TEXT_GRAPHICS,
+    attribute: TEXT_ATTRIBUTE,
+};
+
+const GRAPHICS_320X200: registers::RegisterSet = registers::RegisterSet {
+    misc_output: 0x63,
+    sequencer: [0x03, 0x01, 0x0f, 0x00, 0x0e],
+    crtc: [
+        0x5f, 0x4f, 0x50, 0x82, 0x54, 0x80, 0xbf, 0x1f, 0x00, 0x41, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x9c, 0x0e, 0x8f, 0x28, 0x40, 0x96, 0xb9, 0xa3, 0xff,
+    ],
+    graphics: [0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0x05, 0x0f, 0xff],
+    attribute: [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f, 0x41, 0x00, 0x0f, 0x00, 0x00,
+    ],
+};
@@ -0,0 +1,115 @@
+use super::color;
+use super::registers;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rgb {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Rgb {
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
+
+    pub const fn from_hex(value: u32) -> Self {
+        Self::new((value >> 16) as u8, (value >> 8) as u8, value as u8)
+    }
+
+    const fn to_dac(self) -> (u8, u8, u8) {
+        (self.red >> 2, self.green >> 2, self.blue >> 2)
+    }
+
+    const fn from_dac(value: (u8, u8, u8)) -> Self {
+        const fn expand(component: u8) -> u8 {
+            let component = component & 0x3f;
+            (component << 2) | (component >> 4)
+        }
+
+        Self::new(expand(value.0), expand(value.1), expand(value.2))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    colors: [Rgb; 16],
+}
+
+impl Palette {
+    pub const fn new(colors: [Rgb; 16]) -> Self {
+        Self { colors }
+    }
+
+    pub const fn get(&self, color: color::Color) -> Rgb {
+        self.colors[color.as_byte() as usize]
+    }
+
+    pub fn set(&mut self, color: color::Color, value: Rgb) {
+        self.colors[color.as_byte() as usize] = value;
+    }
+
+    pub(super) fn colors(&self) -> impl Iterator<Item = (u8, Rgb)> + '_ {
+        self.colors
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (index as u8, *value))
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::new([
+            Rgb::from_hex(0x000000),
+            Rgb::from_hex(0x0000aa),
+            Rgb::from_hex(0x00aa00),
+            Rgb::from_hex(0x00aaaa),
+            Rgb::from_hex(0xaa0000),
+            Rgb::from_hex(0xaa00aa),
+            Rgb::from_hex(0xaa5500),
+            Rgb::from_hex(0xaaaaaa),
+            Rgb::from_hex(0x555555),
+            Rgb::from_hex(0x5555ff),
+            Rgb::from_hex(0x55ff55),
+            Rgb::from_hex(0x55ffff),
+            Rgb::from_hex(0xff5555),
+            Rgb::from_hex(0xff55ff),
+            Rgb::from_hex(0xffff55),
+            Rgb::from_hex(0xffffff),
+        ])
+    }
+}
+
+pub(super) fn read(index: u8) -> Rgb {
+    Rgb::from_dac(registers::read_dac(index))
+}
+
+pub(super) fn write(index: u8, value: Rgb) {
+    registers::write_dac(index, value.to_dac());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_dac_round_trip() {
+        let color = Rgb::from_hex(0xff5500);
+
+        assert_eq!(color.to_dac(), (0x3f, 0x15, 0x00));
+        assert_eq!(Rgb::from_dac(color.to_dac()), color);
+    }
+
+    #[kernel_test]
+    fn test_palette_indexing() {
+        let mut palette = Palette::default();
+        let color = color::Color::Bright(color::ColorName::Yellow);
+
+        assert_eq!(palette.get(color), Rgb::from_hex(0xffff55));
+
+        palette.set(color, Rgb::new(1, 2, 3));
+        assert_eq!(palette.get(color), Rgb::new(1, 2, 3));
+        assert_eq!(palette.colors().nth(14), Some((14, Rgb::new(1, 2, 3))));
+    }
+}
@@ -0,0 +1,172 @@
+use super::font;
+
+const PSF1_MAGIC: [u8; 2] = [0x36, 0x04];
+const PSF1_HEADER_SIZE: usize = 4;
+
+const PSF2_MAGIC: [u8; 4] = [0x72, 0xb5, 0x4a, 0x86];
+const PSF2_HEADER_SIZE: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PsfError {
+    InvalidMagic,
+    UnsupportedWidth(u32),
+    UnsupportedHeight(u32),
+    InvalidGlyphSize(u32),
+    Truncated,
+}
+
+struct Header {
+    offset: usize,
+    glyph_count: usize,
+    glyph_size: usize,
+    height: usize,
+}
+
+impl font::Font {
+    pub fn from_psf(bytes: &[u8]) -> Result<font::Font, PsfError> {
+        let header = if bytes.starts_with(&PSF1_MAGIC) {
+            parse_psf1(bytes)?
+        } else if bytes.starts_with(&PSF2_MAGIC) {
+            parse_psf2(bytes)?
+        } else {
+            return Err(PsfError::InvalidMagic);
+        };
+
+        let glyph_count = core::cmp::min(header.glyph_count, font::GLYPH_COUNT);
+        let end = header.offset + glyph_count * header.glyph_size;
+
+        if bytes.len() < end {
+            return Err(PsfError::Truncated);
+        }
+
+        let mut font = font::Font::empty(header.height);
+        let glyphs = bytes[header.offset..end].chunks_exact(header.glyph_size);
+
+        for (codepoint, rows) in glyphs.enumerate() {
+            font.set_glyph(codepoint as u8, rows);
+        }
+
+        Ok(font)
+    }
+}
+
+fn parse_psf1(bytes: &[u8]) -> Result<Header, PsfError> {
+    if bytes.len() < PSF1_HEADER_SIZE {
+        return Err(PsfError::Truncated);
+    }
+
+    let mode = bytes[2];
+    let height = bytes[3] as usize;
+
+    check_height(height as u32)?;
+
+    Ok(Header {
+        offset: PSF1_HEADER_SIZE,
+        glyph_count: if mode & 0x01 != 0 { 512 } else { 256 },
+        glyph_size: height,
+        height,
+    })
+}
+
+fn parse_psf2(bytes: &[u8]) -> Result<Header, PsfError> {
+    if bytes.len() < PSF2_HEADER_SIZE {
+        return Err(PsfError::Truncated);
+    }
+
+    let field = |index: usize| {
+        let start = 4 * index;
+        u32::from_le_bytes([
+            bytes[start],
+            bytes[start + 1],
+            bytes[start + 2],
+            bytes[start + 3],
+        ])
+    };
+
+    let (header_size, glyph_count, glyph_size) = (field(2), field(4), field(5));
+    let (height, width) = (field(6), field(7));
+
+    if width != 8 {
+        return Err(PsfError::UnsupportedWidth(width));
+    }
+
+    check_height(height)?;
+
+    if glyph_size != height {
+        return Err(PsfError::InvalidGlyphSize(glyph_size));
+    }
+
+    Ok(Header {
+        offset: header_size as usize,
+        glyph_count: glyph_count as usize,
+        glyph_size: glyph_size as usize,
+        height: height as usize,
+    })
+}
+
+fn check_height(height: u32) -> Result<(), PsfError> {
+    match height {
+        1..=32 => Ok(()),
+        _ => Err(PsfError::UnsupportedHeight(height)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_psf1() {
+        let mut bytes = [0u8; PSF1_HEADER_SIZE + 256 * 2];
+        bytes[..4].copy_from_slice(&[0x36, 0x04, 0x00, 0x02]);
+        bytes[PSF1_HEADER_SIZE + 2 * b'a' as usize] = 0x18;
+        bytes[PSF1_HEADER_SIZE + 2 * b'a' as usize + 1] = 0x3c;
+
+        let font = font::Font::from_psf(&bytes).unwrap();
+
+        assert_eq!(font.height(), 2);
+        assert_eq!(font.glyph(b'a'), &[0x18, 0x3c]);
+        assert_eq!(
+            font::Font::from_psf(&bytes[..100]).err(),
+            Some(PsfError::Truncated)
+        );
+    }
+
+    #[kernel_test]
+    fn test_psf2() {
+        let mut bytes = [0u8; PSF2_HEADER_SIZE + 2 * 3];
+        let header: [u32; 8] = [0x864a_b572, 0, PSF2_HEADER_SIZE as u32, 0, 2, 3, 3, 8];
+
+        for (index, value) in header.iter().enumerate() {
+            bytes[4 * index..4 * index + 4].copy_from_slice(&value.to_le_bytes());
+        }
+        bytes[PSF2_HEADER_SIZE + 3..].copy_from_slice(&[1, 2, 3]);
+
+        let font = font::Font::from_psf(&bytes).unwrap();
+
+        assert_eq!(font.height(), 3);
+        assert_eq!(font.glyph(1), &[1, 2, 3]);
+        assert_eq!(font.glyph(2), &[0, 0, 0]);
+    }
+
+    #[kernel_test]
+    fn test_invalid_psf() {
+        assert_eq!(
+            font::Font::from_psf(&[0, 1, 2, 3]).err(),
+            Some(PsfError::InvalidMagic)
+        );
+
+        let mut bytes = [0u8; PSF2_HEADER_SIZE];
+        let header: [u32; 8] = [0x864a_b572, 0, PSF2_HEADER_SIZE as u32, 0, 0, 32, 16, 16];
+
+        for (index, value) in header.iter().enumerate() {
+            bytes[4 * index..4 * index + 4].copy_from_slice(&value.to_le_bytes());
+        }
+
+        assert_eq!(
+            font::Font::from_psf(&bytes).err(),
+            Some(PsfError::UnsupportedWidth(16))
+        );
+    }
+}
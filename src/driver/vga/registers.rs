@@ -0,0 +1,160 @@
+use x86_64::instructions::port::Port;
+
+const MISC_OUTPUT_WRITE: u16 = 0x3c2;
+const SEQUENCER_INDEX: u16 = 0x3c4;
+const SEQUENCER_DATA: u16 = 0x3c5;
+const GRAPHICS_INDEX: u16 = 0x3ce;
+const GRAPHICS_DATA: u16 = 0x3cf;
+const CRTC_INDEX: u16 = 0x3d4;
+const CRTC_DATA: u16 = 0x3d5;
+const ATTRIBUTE_INDEX: u16 = 0x3c0;
+const ATTRIBUTE_READ: u16 = 0x3c1;
+const INPUT_STATUS: u16 = 0x3da;
+const DAC_READ_INDEX: u16 = 0x3c7;
+const DAC_WRITE_INDEX: u16 = 0x3c8;
+const DAC_DATA: u16 = 0x3c9;
+
+const ATTRIBUTE_PALETTE_ADDRESS_SOURCE: u8 = 0x20;
+
+const FONT_PLANE_ADDRESS: usize = 0xa0000;
+pub(super) const FONT_PLANE_SIZE: usize = 0x2000;
+
+pub(super) struct RegisterSet {
+    pub(super) misc_output: u8,
+    pub(super) sequencer: [u8; 5],
+    pub(super) crtc: [u8; 25],
+    pub(super) graphics: [u8; 9],
+    pub(super) attribute: [u8; 21],
+}
+
+pub(super) fn load(registers: &RegisterSet) {
+    write(MISC_OUTPUT_WRITE, registers.misc_output);
+
+    for (index, value) in registers.sequencer.iter().enumerate() {
+        write_sequencer(index as u8, *value);
+    }
+
+    write_crtc(0x03, read_crtc(0x03) | 0x80);
+    write_crtc(0x11, read_crtc(0x11) & !0x80);
+
+    for (index, value) in registers.crtc.iter().enumerate() {
+        let value = match index {
+            0x03 => *value | 0x80,
+            0x11 => *value & !0x80,
+            _ => *value,
+        };
+        write_crtc(index as u8, value);
+    }
+
+    for (index, value) in registers.graphics.iter().enumerate() {
+        write_graphics(index as u8, *value);
+    }
+
+    for (index, value) in registers.attribute.iter().enumerate() {
+        write_attribute(index as u8, *value);
+    }
+}
+
+pub(super) fn read_sequencer(index: u8) -> u8 {
+    write(SEQUENCER_INDEX, index);
+    read(SEQUENCER_DATA)
+}
+
+pub(super) fn write_sequencer(index: u8, value: u8) {
+    write(SEQUENCER_INDEX, index);
+    write(SEQUENCER_DATA, value);
+}
+
+pub(super) fn read_crtc(index: u8) -> u8 {
+    write(CRTC_INDEX, index);
+    read(CRTC_DATA)
+}
+
+pub(super) fn write_crtc(index: u8, value: u8) {
+    write(CRTC_INDEX, index);
+    write(CRTC_DATA, value);
+}
+
+pub(super) fn read_graphics(index: u8) -> u8 {
+    write(GRAPHICS_INDEX, index);
+    read(GRAPHICS_DATA)
+}
+
+pub(super) fn write_graphics(index: u8, value: u8) {
+    write(GRAPHICS_INDEX, index);
+    write(GRAPHICS_DATA, value);
+}
+
+pub(super) fn read_attribute(index: u8) -> u8 {
+    read(INPUT_STATUS);
+    write(ATTRIBUTE_INDEX, index);
+    let value = read(ATTRIBUTE_READ);
+
+    read(INPUT_STATUS);
+    write(ATTRIBUTE_INDEX, ATTRIBUTE_PALETTE_ADDRESS_SOURCE);
+
+    value
+}
+
+pub(super) fn write_attribute(index: u8, value: u8) {
+    read(INPUT_STATUS);
+    write(ATTRIBUTE_INDEX, index);
+    write(ATTRIBUTE_INDEX, value);
+
+    read(INPUT_STATUS);
+    write(ATTRIBUTE_INDEX, ATTRIBUTE_PALETTE_ADDRESS_SOURCE);
+}
+
+pub(super) fn read_dac(index: u8) -> (u8, u8, u8) {
+    write(DAC_READ_INDEX, index);
+    (read(DAC_DATA), read(DAC_DATA), read(DAC_DATA))
+}
+
+pub(super) fn write_dac(index: u8, color: (u8, u8, u8)) {
+    write(DAC_WRITE_INDEX, index);
+    write(DAC_DATA, color.0);
+    write(DAC_DATA, color.1);
+    write(DAC_DATA, color.2);
+}
+
+pub(super) fn with_font_plane<F, R>(function: F) -> R
+where
+    F: FnOnce(&mut [u8; FONT_PLANE_SIZE]) -> R,
+{
+    let map_mask = read_sequencer(0x02);
+    let memory_mode = read_sequencer(0x04);
+    let read_map = read_graphics(0x04);
+    let graphics_mode = read_graphics(0x05);
+    let miscellaneous = read_graphics(0x06);
+
+    write_sequencer(0x00, 0x01);
+    write_sequencer(0x02, 0x04);
+    write_sequencer(0x04, 0x07);
+    write_sequencer(0x00, 0x03);
+
+    write_graphics(0x04, 0x02);
+    write_graphics(0x05, 0x00);
+    write_graphics(0x06, 0x00);
+
+    let plane = unsafe { &mut *(FONT_PLANE_ADDRESS as *mut [u8; FONT_PLANE_SIZE]) };
+    let result = function(plane);
+
+    write_sequencer(0x00, 0x01);
+    write_sequencer(0x02, map_mask);
+    write_sequencer(0x04, memory_mode);
+    write_sequencer(0x00, 0x03);
+
+    write_graphics(0x04, read_map);
+    write_graphics(0x05, graphics_mode);
+    write_graphics(0x06, miscellaneous);
+
+    result
+}
+
+fn read(port: u16) -> u8 {
+    unsafe { Port::<u8>::new(port).read() }
+}
+
+fn write(port: u16, value: u8) {
+    unsafe { Port::<u8>::new(port).write(value) }
+}
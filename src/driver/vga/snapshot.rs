@@ -0,0 +1,372 @@
+use super::buffer;
+use crate::libs::buffer::GridBuffer;
+use core::fmt;
+
+pub const SNAPSHOT_WIDTH: usize = 80;
+pub const SNAPSHOT_HEIGHT: usize = 25;
+
+const HEADER: &str = "snapshot";
+const DIMENSIONS: &str = "80x25";
+const TEXT_PREFIX: char = '|';
+const ATTRIBUTE_PREFIX: char = ':';
+
+type Row = [u16; SNAPSHOT_WIDTH];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Snapshot {
+    cells: GridBuffer<u16, SNAPSHOT_WIDTH, SNAPSHOT_HEIGHT>,
+}
+
+impl Snapshot {
+    pub fn from_cells(cells: [[u16; SNAPSHOT_WIDTH]; SNAPSHOT_HEIGHT]) -> Self {
+        Self {
+            cells: GridBuffer::from_array(cells),
+        }
+    }
+
+    /// Snapshots only cover 80x25 text mode, so any other mode panics instead of dropping cells.
+    pub(super) fn capture(buffer: &buffer::VGABuffer) -> Self {
+        assert!(
+            buffer.width() == SNAPSHOT_WIDTH && buffer.height() == SNAPSHOT_HEIGHT,
+            "snapshots need {} text mode, not {}x{}",
+            DIMENSIONS,
+            buffer.width(),
+            buffer.height()
+        );
+
+        let mut cells = [[0; SNAPSHOT_WIDTH]; SNAPSHOT_HEIGHT];
+
+        for (line, row) in cells.iter_mut().enumerate() {
+            for (column, cell) in row.iter_mut().enumerate() {
+                *cell = buffer.get((line, column));
+            }
+        }
+
+        Self::from_cells(cells)
+    }
+
+    pub fn cell(&self, position: (usize, usize)) -> (u8, u8) {
+        let value = self.cells.content[position.0][position.1];
+        (value as u8, (value >> 8) as u8)
+    }
+
+    pub fn parse(text: &str) -> Option<Snapshot> {
+        let mut lines = text.lines();
+        let mut header = lines.next()?.split_whitespace();
+
+        if header.next()? != HEADER || header.next()? != DIMENSIONS {
+            return None;
+        }
+
+        let default = u8::from_str_radix(header.next()?, 16).ok()?;
+        let mut cells = [[(default as u16) << 8; SNAPSHOT_WIDTH]; SNAPSHOT_HEIGHT];
+        let mut count = 0;
+
+        for line in lines {
+            if let Some(text) = line.strip_prefix(TEXT_PREFIX) {
+                parse_text(text, cells.get_mut(count)?)?;
+                count += 1;
+            } else if let Some(attributes) = line.strip_prefix(ATTRIBUTE_PREFIX) {
+                parse_attributes(attributes, cells.get_mut(count.checked_sub(1)?)?)?;
+            } else if !line.is_empty() {
+                return None;
+            }
+        }
+
+        match count == SNAPSHOT_HEIGHT {
+            true => Some(Self::from_cells(cells)),
+            false => None,
+        }
+    }
+
+    pub fn diff<'a>(&'a self, expected: Option<&'a Snapshot>) -> Diff<'a> {
+        Diff {
+            expected,
+            actual: self,
+        }
+    }
+
+    fn default_attribute(&self) -> u8 {
+        let mut counts = [0usize; 256];
+
+        for (_, cell) in self.cells.cells() {
+            counts[(cell >> 8) as usize] += 1;
+        }
+
+        (0..=255u8)
+            .max_by_key(|attribute| (counts[*attribute as usize], u8::MAX - *attribute))
+            .unwrap_or(0)
+    }
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let default = self.default_attribute();
+        writeln!(formatter, "{} {} {:02x}", HEADER, DIMENSIONS, default)?;
+
+        for row in self.cells.rows() {
+            writeln!(formatter, "{}{}", TEXT_PREFIX, Text(row))?;
+
+            if row.iter().any(|cell| (cell >> 8) as u8 != default) {
+                writeln!(
+                    formatter,
+                    "{}{}",
+                    ATTRIBUTE_PREFIX,
+                    Attributes(row, Some(default))
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct Diff<'a> {
+    expected: Option<&'a Snapshot>,
+    actual: &'a Snapshot,
+}
+
+impl fmt::Display for Diff<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let expected = match self.expected {
+            Some(expected) => expected,
+            None => {
+                return write!(
+                    formatter,
+                    "no valid stored snapshot, actual:\n{}",
+                    self.actual
+                )
+            }
+        };
+
+        let rows = expected.cells.rows().zip(self.actual.cells.rows());
+
+        for (line, (expected, actual)) in rows.enumerate() {
+            let text_differs = expected
+                .iter()
+                .zip(actual.iter())
+                .any(|(expected, actual)| *expected as u8 != *actual as u8);
+            let attributes_differ = expected
+                .iter()
+                .zip(actual.iter())
+                .any(|(expected, actual)| expected >> 8 != actual >> 8);
+
+            if !text_differs && !attributes_differ {
+                continue;
+            }
+
+            writeln!(formatter, "line {}:", line)?;
+
+            if text_differs {
+                writeln!(formatter, "- {}{}", TEXT_PREFIX, Text(expected))?;
+                writeln!(formatter, "+ {}{}", TEXT_PREFIX, Text(actual))?;
+            }
+
+            if attributes_differ {
+                writeln!(
+                    formatter,
+                    "- {}{}",
+                    ATTRIBUTE_PREFIX,
+                    Attributes(expected, None)
+                )?;
+                writeln!(
+                    formatter,
+                    "+ {}{}",
+                    ATTRIBUTE_PREFIX,
+                    Attributes(actual, None)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct Text<'a>(&'a Row);
+
+impl fmt::Display for Text<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let length = self
+            .0
+            .iter()
+            .rposition(|cell| *cell as u8 != 0)
+            .map_or(0, |position| position + 1);
+
+        for cell in self.0[..length].iter() {
+            match *cell as u8 {
+                0 => formatter.write_str("\\0")?,
+                b'\\' => formatter.write_str("\\\\")?,
+                codepoint @ 0x20..=0x7e => fmt::Write::write_char(formatter, codepoint as char)?,
+                codepoint => write!(formatter, "\\x{:02x}", codepoint)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct Attributes<'a>(&'a Row, Option<u8>);
+
+impl fmt::Display for Attributes<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let attribute = |index: usize| (self.0[index] >> 8) as u8;
+
+        let length = match self.1 {
+            Some(default) => (0..SNAPSHOT_WIDTH)
+                .rposition(|index| attribute(index) != default)
+                .map_or(0, |position| position + 1),
+            None => SNAPSHOT_WIDTH,
+        };
+
+        let mut start = 0;
+
+        while start < length {
+            let value = attribute(start);
+            let end = (start..length)
+                .find(|index| attribute(*index) != value)
+                .unwrap_or(length);
+
+            if start > 0 {
+                formatter.write_str(" ")?;
+            }
+
+            match end - start {
+                1 => write!(formatter, "{:02x}", value)?,
+                count => write!(formatter, "{:02x}*{}", value, count)?,
+            }
+
+            start = end;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_text(text: &str, row: &mut Row) -> Option<()> {
+    let mut bytes = text.bytes();
+    let mut column = 0;
+
+    while let Some(byte) = bytes.next() {
+        let codepoint = match byte {
+            b'\\' => match bytes.next()? {
+                b'\\' => b'\\',
+                b'0' => 0,
+                b'x' => {
+                    let digits = [bytes.next()?, bytes.next()?];
+                    u8::from_str_radix(core::str::from_utf8(&digits).ok()?, 16).ok()?
+                }
+                _ => return None,
+            },
+            0x20..=0x7e => byte,
+            _ => return None,
+        };
+
+        let cell = row.get_mut(column)?;
+        *cell = (*cell & 0xff00) | codepoint as u16;
+        column += 1;
+    }
+
+    Some(())
+}
+
+fn parse_attributes(attributes: &str, row: &mut Row) -> Option<()> {
+    let mut column = 0;
+
+    for run in attributes.split_whitespace() {
+        let (value, count) = match run.split_once('*') {
+            Some((value, count)) => (value, count.parse::<usize>().ok()?),
+            None => (run, 1),
+        };
+        let value = u8::from_str_radix(value, 16).ok()?;
+
+        for cell in row.get_mut(column..column + count)? {
+            *cell = (*cell & 0x00ff) | (value as u16) << 8;
+        }
+
+        column += count;
+    }
+
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::format::FixedString;
+    use crate::libs::testing::kernel_test;
+    use core::fmt::Write;
+
+    fn sample() -> Snapshot {
+        let mut cells = [[0x0f00; SNAPSHOT_WIDTH]; SNAPSHOT_HEIGHT];
+
+        for (column, codepoint) in b"ok\\\x01".iter().enumerate() {
+            cells[0][column] |= *codepoint as u16;
+        }
+        cells[0][1] = 0x4e00 | b'k' as u16;
+        cells[24][79] = 0x1f00 | b'z' as u16;
+
+        Snapshot::from_cells(cells)
+    }
+
+    #[kernel_test(should_panic(expected = "snapshots need 80x25 text mode, not 90x60"))]
+    fn test_capture_other_mode() {
+        let buffer = buffer::testing::construct_buffer_from_strings(&["ok"]);
+        Snapshot::capture(&buffer);
+    }
+
+    #[kernel_test]
+    fn test_serialize() {
+        let mut text = FixedString::<512>::new();
+        write!(text, "{}", sample()).unwrap();
+
+        let mut lines = text.as_str().lines();
+
+        assert_eq!(lines.next(), Some("snapshot 80x25 0f"));
+        assert_eq!(lines.next(), Some("|ok\\\\\\x01"));
+        assert_eq!(lines.next(), Some(":0f 4e"));
+        assert_eq!(lines.last(), Some(":0f*79 1f"));
+    }
+
+    #[kernel_test]
+    fn test_parse_round_trip() {
+        let mut text = FixedString::<512>::new();
+        write!(text, "{}", sample()).unwrap();
+
+        let snapshot = Snapshot::parse(text.as_str()).unwrap();
+
+        assert_eq!(snapshot, sample());
+        assert_eq!(snapshot.cell((0, 1)), (b'k', 0x4e));
+        assert_eq!(Snapshot::parse(""), None);
+        assert_eq!(Snapshot::parse("snapshot 80x25 0f\n|a"), None);
+    }
+
+    #[kernel_test]
+    fn test_diff() {
+        let expected = sample();
+        let mut cells = expected.cells.content;
+        cells[3][0] = 0x0c00 | b'x' as u16;
+        let actual = Snapshot::from_cells(cells);
+
+        let mut text = FixedString::<512>::new();
+        write!(text, "{}", actual.diff(Some(&expected))).unwrap();
+
+        let mut lines = text.as_str().lines();
+
+        assert_eq!(lines.next(), Some("line 3:"));
+        assert_eq!(lines.next(), Some("- |"));
+        assert_eq!(lines.next(), Some("+ |x"));
+        assert_eq!(lines.next(), Some("- :0f*80"));
+        assert_eq!(lines.next(), Some("+ :0c 0f*79"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[kernel_test]
+    fn test_parse_stored_snapshot() {
+        let snapshot = Snapshot::parse(include_str!("snapshots/styled_text.snap")).unwrap();
+
+        assert_eq!(snapshot.cell((0, 8)), (b' ', 0x0f));
+        assert_eq!(snapshot.cell((0, 9)), (b'r', 0x0c));
+        assert_eq!(snapshot.cell((1, 0)), (b'\\', 0x0f));
+        assert_eq!(snapshot.cell((24, 79)), (0, 0));
+    }
+}
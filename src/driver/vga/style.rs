@@ -0,0 +1,117 @@
+use super::attribute;
+use super::color;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackgroundMode {
+    Blink,
+    Bright,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    background: color::Color,
+    foreground: color::Color,
+    blink: bool,
+}
+
+impl Style {
+    pub const fn new() -> Self {
+        Self {
+            background: color::Color::Dim(color::ColorName::Black),
+            foreground: color::Color::Bright(color::ColorName::White),
+            blink: false,
+        }
+    }
+
+    pub const fn background(mut self, background: color::Color) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub const fn foreground(mut self, foreground: color::Color) -> Self {
+        self.foreground = foreground;
+        self
+    }
+
+    pub const fn blink(mut self, blink: bool) -> Self {
+        self.blink = blink;
+        self
+    }
+
+    pub const fn emphasis(mut self) -> Self {
+        self.foreground = self.foreground.bright();
+        self
+    }
+
+    pub const fn inverted(mut self) -> Self {
+        let background = self.background;
+        self.background = self.foreground;
+        self.foreground = background;
+        self
+    }
+
+    pub(super) const fn attribute(self, mode: BackgroundMode) -> attribute::Attribute {
+        let background = match (mode, self.blink) {
+            (BackgroundMode::Bright, _) => self.background,
+            (BackgroundMode::Blink, true) => self.background.bright(),
+            (BackgroundMode::Blink, false) => self.background.dim(),
+        };
+
+        attribute::Attribute::new(background, self.foreground)
+    }
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_attribute_in_blink_mode() {
+        let style = Style::new()
+            .background(color::Color::Bright(color::ColorName::Blue))
+            .foreground(color::Color::Dim(color::ColorName::Yellow));
+
+        assert_eq!(style.attribute(BackgroundMode::Blink).as_byte(), 0x16);
+        assert_eq!(
+            style.blink(true).attribute(BackgroundMode::Blink).as_byte(),
+            0x96
+        );
+    }
+
+    #[kernel_test]
+    fn test_attribute_in_bright_mode() {
+        let style = Style::new()
+            .background(color::Color::Bright(color::ColorName::Blue))
+            .foreground(color::Color::Dim(color::ColorName::Yellow))
+            .blink(true);
+
+        assert_eq!(style.attribute(BackgroundMode::Bright).as_byte(), 0x96);
+        assert_eq!(
+            style
+                .background(color::Color::Dim(color::ColorName::Blue))
+                .attribute(BackgroundMode::Bright)
+                .as_byte(),
+            0x16
+        );
+    }
+
+    #[kernel_test]
+    fn test_emphasis_and_inverted() {
+        let style = Style::new()
+            .foreground(color::Color::Dim(color::ColorName::Green))
+            .emphasis()
+            .inverted();
+
+        assert_eq!(
+            style.attribute(BackgroundMode::Bright).as_byte(),
+            (color::Color::Bright(color::ColorName::Green).as_byte() << 4)
+        );
+    }
+}
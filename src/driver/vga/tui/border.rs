@@ -0,0 +1,39 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Border {
+    #[default]
+    Single,
+    Double,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct BorderGlyphs {
+    pub(super) top_left: u8,
+    pub(super) top_right: u8,
+    pub(super) bottom_left: u8,
+    pub(super) bottom_right: u8,
+    pub(super) horizontal: u8,
+    pub(super) vertical: u8,
+}
+
+impl Border {
+    pub(super) const fn glyphs(self) -> BorderGlyphs {
+        match self {
+            Border::Single => BorderGlyphs {
+                top_left: 0xda,
+                top_right: 0xbf,
+                bottom_left: 0xc0,
+                bottom_right: 0xd9,
+                horizontal: 0xc4,
+                vertical: 0xb3,
+            },
+            Border::Double => BorderGlyphs {
+                top_left: 0xc9,
+                top_right: 0xbb,
+                bottom_left: 0xc8,
+                bottom_right: 0xbc,
+                horizontal: 0xcd,
+                vertical: 0xba,
+            },
+        }
+    }
+}
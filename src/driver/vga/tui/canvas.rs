@@ -0,0 +1,241 @@
+use super::super::attribute;
+use super::super::character;
+use super::super::color;
+use super::super::mode;
+use super::super::style;
+use super::border::Border;
+use crate::libs::buffer::{GridBuffer, Region};
+
+pub struct Canvas<'a> {
+    cells: &'a mut [u16],
+    stride: usize,
+    region: Region,
+    style: attribute::Attribute,
+}
+
+impl<'a> Canvas<'a> {
+    pub fn new<const WIDTH: usize, const HEIGHT: usize>(
+        buffer: &'a mut GridBuffer<u16, WIDTH, HEIGHT>,
+    ) -> Self {
+        Self {
+            cells: buffer.content.as_flattened_mut(),
+            stride: WIDTH,
+            region: Region::full(HEIGHT, WIDTH),
+            style: attribute::Attribute::default(),
+        }
+    }
+
+    pub fn clipped(mut self, region: Region) -> Self {
+        self.region = self.region.intersect(&region);
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.region.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.region.height
+    }
+
+    pub fn bounds(&self) -> Region {
+        Region::full(self.region.height, self.region.width)
+    }
+
+    pub fn sub(&mut self, region: Region) -> Canvas<'_> {
+        let region = region.clip(self.region.height, self.region.width);
+
+        Canvas {
+            cells: self.cells,
+            stride: self.stride,
+            region: Region::new(
+                (
+                    self.region.line + region.line,
+                    self.region.column + region.column,
+                ),
+                region.size(),
+            ),
+            style: self.style,
+        }
+    }
+
+    pub fn set_style(&mut self, background: color::Color, foreground: color::Color) {
+        self.style = attribute::Attribute::new(background, foreground);
+    }
+
+    pub fn apply_style(&mut self, style: style::Style) {
+        self.style = style.attribute(mode::background_mode());
+    }
+
+    pub fn reset_style(&mut self) {
+        self.style = attribute::Attribute::default();
+    }
+
+    pub fn set_background(&mut self, background: color::Color) {
+        self.style.background = background;
+    }
+
+    pub fn set_foreground(&mut self, foreground: color::Color) {
+        self.style.foreground = foreground;
+    }
+
+    pub(super) fn style(&self) -> attribute::Attribute {
+        self.style
+    }
+
+    pub(super) fn set_attribute(&mut self, style: attribute::Attribute) {
+        self.style = style;
+    }
+
+    pub fn clear(&mut self) {
+        self.fill(self.bounds(), b' ');
+    }
+
+    pub fn put(&mut self, position: (usize, usize), glyph: u8) {
+        let (line, column) = position;
+
+        if line >= self.region.height || column >= self.region.width {
+            return;
+        }
+
+        let index = (self.region.line + line) * self.stride + self.region.column + column;
+        self.cells[index] = character::Character::glyph(glyph, self.style).into();
+    }
+
+    pub fn write_str(&mut self, position: (usize, usize), string: &str) -> usize {
+        let (line, column) = position;
+        let mut written = 0;
+
+        for byte in string.bytes() {
+            if column + written >= self.region.width {
+                break;
+            }
+
+            let value = character::Character::new(byte, self.style).codepoint;
+            self.put((line, column + written), value);
+            written += 1;
+        }
+
+        written
+    }
+
+    pub fn fill(&mut self, region: Region, glyph: u8) {
+        let region = region.clip(self.region.height, self.region.width);
+
+        for line in region.lines() {
+            for column in region.columns() {
+                self.put((line, column), glyph);
+            }
+        }
+    }
+
+    pub fn horizontal_line(&mut self, position: (usize, usize), length: usize, glyph: u8) {
+        self.fill(Region::new(position, (1, length)), glyph);
+    }
+
+    pub fn vertical_line(&mut self, position: (usize, usize), length: usize, glyph: u8) {
+        self.fill(Region::new(position, (length, 1)), glyph);
+    }
+
+    pub fn frame(&mut self, region: Region, border: Border) {
+        let region = region.clip(self.region.height, self.region.width);
+
+        if region.width < 2 || region.height < 2 {
+            return;
+        }
+
+        let glyphs = border.glyphs();
+        let (last_line, last_column) = (region.end().0 - 1, region.end().1 - 1);
+
+        self.horizontal_line(
+            (region.line, region.column + 1),
+            region.width - 2,
+            glyphs.horizontal,
+        );
+        self.horizontal_line(
+            (last_line, region.column + 1),
+            region.width - 2,
+            glyphs.horizontal,
+        );
+        self.vertical_line(
+            (region.line + 1, region.column),
+            region.height - 2,
+            glyphs.vertical,
+        );
+        self.vertical_line(
+            (region.line + 1, last_column),
+            region.height - 2,
+            glyphs.vertical,
+        );
+
+        self.put((region.line, region.column), glyphs.top_left);
+        self.put((region.line, last_column), glyphs.top_right);
+        self.put((last_line, region.column), glyphs.bottom_left);
+        self.put((last_line, last_column), glyphs.bottom_right);
+    }
+}
+
+#[cfg(test)]
+pub(super) mod testing {
+    use super::*;
+
+    pub fn line_text<const WIDTH: usize, const HEIGHT: usize>(
+        buffer: &GridBuffer<u16, WIDTH, HEIGHT>,
+        line: usize,
+    ) -> [u8; WIDTH] {
+        let mut text = [0; WIDTH];
+
+        for (column, value) in buffer.content[line].iter().enumerate() {
+            text[column] = character::Character::from(*value).codepoint;
+        }
+
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_write_str_clips_to_region() {
+        let mut buffer = GridBuffer::<u16, 8, 3>::new();
+        let mut canvas = Canvas::new(&mut buffer);
+
+        let written = canvas
+            .sub(Region::new((1, 2), (1, 4)))
+            .write_str((0, 1), "abcdef");
+
+        assert_eq!(written, 3);
+        assert_eq!(&testing::line_text(&buffer, 1), b"\0\0\0abc\0\0");
+        assert_eq!(&testing::line_text(&buffer, 0), &[0; 8]);
+    }
+
+    #[kernel_test]
+    fn test_frame() {
+        let mut buffer = GridBuffer::<u16, 4, 3>::new();
+        Canvas::new(&mut buffer).frame(Region::full(3, 4), Border::Double);
+
+        assert_eq!(testing::line_text(&buffer, 0), [0xc9, 0xcd, 0xcd, 0xbb]);
+        assert_eq!(testing::line_text(&buffer, 1), [0xba, 0, 0, 0xba]);
+        assert_eq!(testing::line_text(&buffer, 2), [0xc8, 0xcd, 0xcd, 0xbc]);
+    }
+
+    #[kernel_test]
+    fn test_style() {
+        let mut buffer = GridBuffer::<u16, 2, 1>::new();
+        let mut canvas = Canvas::new(&mut buffer);
+
+        canvas.set_style(
+            color::Color::Dim(color::ColorName::Blue),
+            color::Color::Bright(color::ColorName::Yellow),
+        );
+        canvas.put((0, 0), b'x');
+        canvas.reset_style();
+        canvas.put((0, 1), b'y');
+
+        assert_eq!(buffer.content[0][0], 0x1e00 | b'x' as u16);
+        assert_eq!(buffer.content[0][1], 0x0f00 | b'y' as u16);
+    }
+}
@@ -0,0 +1,128 @@
+use super::super::attribute;
+use super::super::color;
+use super::canvas::Canvas;
+use super::{glyph, Widget};
+
+pub struct Menu<'a> {
+    items: &'a [&'a str],
+    selected: usize,
+    style: attribute::Attribute,
+    highlight: attribute::Attribute,
+}
+
+impl<'a> Menu<'a> {
+    pub fn new(items: &'a [&'a str]) -> Self {
+        Self {
+            items,
+            selected: 0,
+            style: attribute::Attribute::default(),
+            highlight: attribute::Attribute::new(
+                color::Color::Dim(color::ColorName::White),
+                color::Color::Dim(color::ColorName::Black),
+            ),
+        }
+    }
+
+    pub fn style(mut self, background: color::Color, foreground: color::Color) -> Self {
+        self.style = attribute::Attribute::new(background, foreground);
+        self
+    }
+
+    pub fn highlight(mut self, background: color::Color, foreground: color::Color) -> Self {
+        self.highlight = attribute::Attribute::new(background, foreground);
+        self
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn selected_item(&self) -> Option<&'a str> {
+        self.items.get(self.selected).copied()
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index < self.items.len() {
+            self.selected = index;
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.items.is_empty() {
+            self.selected = (self.selected + 1) % self.items.len();
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if !self.items.is_empty() {
+            self.selected = (self.selected + self.items.len() - 1) % self.items.len();
+        }
+    }
+
+    fn first_visible(&self, height: usize) -> usize {
+        match self.selected < height {
+            true => 0,
+            false => self.selected + 1 - height,
+        }
+    }
+}
+
+impl<'a> Widget for Menu<'a> {
+    fn draw(&self, canvas: &mut Canvas) {
+        let previous = canvas.style();
+        let height = canvas.height();
+        let width = canvas.width();
+        let first = self.first_visible(height);
+
+        for (line, (index, item)) in self.items.iter().enumerate().skip(first).enumerate() {
+            if line >= height {
+                break;
+            }
+
+            let (style, marker) = match index == self.selected {
+                true => (self.highlight, glyph::POINTER_RIGHT),
+                false => (self.style, b' '),
+            };
+
+            canvas.set_attribute(style);
+            canvas.horizontal_line((line, 0), width, b' ');
+            canvas.put((line, 0), marker);
+            canvas.write_str((line, 2), item);
+        }
+
+        canvas.set_attribute(previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_selection_wraps() {
+        let items = ["one", "two", "three"];
+        let mut menu = Menu::new(&items);
+
+        menu.select_previous();
+        assert_eq!(menu.selected_item(), Some("three"));
+
+        menu.select_next();
+        menu.select_next();
+        assert_eq!(menu.selected_item(), Some("two"));
+
+        menu.select(7);
+        assert_eq!(menu.selected(), 1);
+    }
+
+    #[kernel_test]
+    fn test_first_visible() {
+        let items = ["a", "b", "c", "d", "e"];
+        let mut menu = Menu::new(&items);
+
+        assert_eq!(menu.first_visible(3), 0);
+
+        menu.select(4);
+        assert_eq!(menu.first_visible(3), 2);
+    }
+}
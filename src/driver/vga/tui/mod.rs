@@ -0,0 +1,32 @@
+mod border;
+mod canvas;
+mod menu;
+mod progress_bar;
+mod screen;
+mod status_bar;
+mod text_pane;
+mod window;
+
+pub use border::Border;
+pub use canvas::Canvas;
+pub use menu::Menu;
+pub use progress_bar::ProgressBar;
+pub use screen::Screen;
+pub use status_bar::StatusBar;
+pub use text_pane::TextPane;
+pub use window::Window;
+
+pub mod glyph {
+    pub const SPACE: u8 = b' ';
+    pub const LIGHT_SHADE: u8 = 0xb0;
+    pub const MEDIUM_SHADE: u8 = 0xb1;
+    pub const DARK_SHADE: u8 = 0xb2;
+    pub const FULL_BLOCK: u8 = 0xdb;
+    pub const POINTER_RIGHT: u8 = 0x10;
+    pub const ARROW_UP: u8 = 0x1e;
+    pub const ARROW_DOWN: u8 = 0x1f;
+}
+
+pub trait Widget {
+    fn draw(&self, canvas: &mut Canvas);
+}
@@ -0,0 +1,81 @@
+use super::super::attribute;
+use super::super::color;
+use super::canvas::Canvas;
+use super::{glyph, Widget};
+
+pub struct ProgressBar {
+    value: usize,
+    max: usize,
+    style: attribute::Attribute,
+}
+
+impl ProgressBar {
+    pub fn new(value: usize, max: usize) -> Self {
+        Self {
+            value,
+            max,
+            style: attribute::Attribute::default(),
+        }
+    }
+
+    pub fn style(mut self, background: color::Color, foreground: color::Color) -> Self {
+        self.style = attribute::Attribute::new(background, foreground);
+        self
+    }
+
+    pub fn percentage(&self) -> usize {
+        if self.max == 0 {
+            return 100;
+        }
+
+        core::cmp::min(self.value, self.max) * 100 / self.max
+    }
+
+    fn filled(&self, width: usize) -> usize {
+        if self.max == 0 {
+            return width;
+        }
+
+        core::cmp::min(self.value, self.max) * width / self.max
+    }
+}
+
+impl Widget for ProgressBar {
+    fn draw(&self, canvas: &mut Canvas) {
+        use core::fmt::Write;
+
+        let previous = canvas.style();
+        canvas.set_attribute(self.style);
+
+        let mut label = crate::libs::format::FixedString::<5>::new();
+        write!(label, " {:>3}%", self.percentage()).ok();
+
+        let width = canvas.width().saturating_sub(label.len());
+        let filled = self.filled(width);
+
+        canvas.horizontal_line((0, 0), filled, glyph::FULL_BLOCK);
+        canvas.horizontal_line((0, filled), width - filled, glyph::LIGHT_SHADE);
+        canvas.write_str((0, width), label.as_str());
+
+        canvas.set_attribute(previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::canvas::testing;
+    use super::*;
+    use crate::libs::buffer::GridBuffer;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_progress_bar() {
+        let mut buffer = GridBuffer::<u16, 9, 1>::new();
+        ProgressBar::new(1, 2).draw(&mut Canvas::new(&mut buffer));
+
+        assert_eq!(
+            testing::line_text(&buffer, 0),
+            [0xdb, 0xdb, 0xb0, 0xb0, b' ', b' ', b'5', b'0', b'%']
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use super::super::buffer;
+use super::super::character;
+use super::canvas::Canvas;
+use crate::libs::buffer::{GridBuffer, Region};
+
+pub struct Screen {
+    buffer: GridBuffer<u16, { buffer::VGA_BUFFER_WIDTH }, { buffer::VGA_BUFFER_HEIGHT }>,
+}
+
+impl Screen {
+    pub fn new() -> Self {
+        Self {
+            buffer: GridBuffer::new_with_default(character::Character::default().into()),
+        }
+    }
+
+    pub fn canvas(&mut self) -> Canvas<'_> {
+        let (width, height) = super::super::VGA_WRITER.lock().dimensions();
+        Canvas::new(&mut self.buffer).clipped(Region::full(height, width))
+    }
+
+    pub fn present(&self) {
+        super::super::VGA_WRITER.lock().draw(&self.buffer);
+    }
+}
+
+impl Default for Screen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,49 @@
+use super::super::attribute;
+use super::super::color;
+use super::canvas::Canvas;
+use super::Widget;
+
+pub struct StatusBar<'a> {
+    left: &'a str,
+    right: &'a str,
+    style: attribute::Attribute,
+}
+
+impl<'a> StatusBar<'a> {
+    pub fn new(left: &'a str) -> Self {
+        Self {
+            left,
+            right: "",
+            style: attribute::Attribute::new(
+                color::Color::Dim(color::ColorName::White),
+                color::Color::Dim(color::ColorName::Black),
+            ),
+        }
+    }
+
+    pub fn right(mut self, right: &'a str) -> Self {
+        self.right = right;
+        self
+    }
+
+    pub fn style(mut self, background: color::Color, foreground: color::Color) -> Self {
+        self.style = attribute::Attribute::new(background, foreground);
+        self
+    }
+}
+
+impl<'a> Widget for StatusBar<'a> {
+    fn draw(&self, canvas: &mut Canvas) {
+        let previous = canvas.style();
+        canvas.set_attribute(self.style);
+
+        let width = canvas.width();
+        canvas.horizontal_line((0, 0), width, b' ');
+
+        let right_start = width.saturating_sub(self.right.len() + 1);
+        canvas.write_str((0, right_start), self.right);
+        canvas.write_str((0, 1), self.left);
+
+        canvas.set_attribute(previous);
+    }
+}
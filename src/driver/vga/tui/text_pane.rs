@@ -0,0 +1,127 @@
+use super::super::attribute;
+use super::super::color;
+use super::canvas::Canvas;
+use super::{glyph, Widget};
+
+pub struct TextPane<'a> {
+    lines: &'a [&'a str],
+    scroll: usize,
+    style: attribute::Attribute,
+}
+
+impl<'a> TextPane<'a> {
+    pub fn new(lines: &'a [&'a str]) -> Self {
+        Self {
+            lines,
+            scroll: 0,
+            style: attribute::Attribute::default(),
+        }
+    }
+
+    pub fn style(mut self, background: color::Color, foreground: color::Color) -> Self {
+        self.style = attribute::Attribute::new(background, foreground);
+        self
+    }
+
+    pub fn scroll(&self) -> usize {
+        self.scroll
+    }
+
+    pub fn scroll_to(&mut self, line: usize) {
+        self.scroll = core::cmp::min(line, self.lines.len().saturating_sub(1));
+    }
+
+    pub fn scroll_by(&mut self, count: isize) {
+        let line = (self.scroll as isize).saturating_add(count);
+        self.scroll_to(core::cmp::max(line, 0) as usize);
+    }
+
+    fn first_visible(&self, height: usize) -> usize {
+        core::cmp::min(self.scroll, self.lines.len().saturating_sub(height))
+    }
+}
+
+impl<'a> Widget for TextPane<'a> {
+    fn draw(&self, canvas: &mut Canvas) {
+        let previous = canvas.style();
+        canvas.set_attribute(self.style);
+        canvas.clear();
+
+        let height = canvas.height();
+        // The scrollbar needs a row for each arrow.
+        let scrollable = self.lines.len() > height && height >= 2 && canvas.width() > 1;
+        let text_width = match scrollable {
+            true => canvas.width() - 1,
+            false => canvas.width(),
+        };
+        let first = self.first_visible(height);
+
+        let mut text = canvas.sub(crate::libs::buffer::Region::new(
+            (0, 0),
+            (height, text_width),
+        ));
+        for (line, content) in self.lines[first..].iter().take(height).enumerate() {
+            text.write_str((line, 0), content);
+        }
+
+        if scrollable {
+            let column = text_width;
+            canvas.vertical_line((0, column), height, glyph::LIGHT_SHADE);
+
+            let thumb = first * (height - 1) / (self.lines.len() - height);
+            canvas.put((thumb, column), glyph::FULL_BLOCK);
+            canvas.put((0, column), glyph::ARROW_UP);
+            canvas.put((height - 1, column), glyph::ARROW_DOWN);
+        }
+
+        canvas.set_attribute(previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::canvas::testing;
+    use super::super::Window;
+    use super::*;
+    use crate::libs::buffer::GridBuffer;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_scrolled_text_pane() {
+        let lines = ["aa", "bb", "cc", "dd"];
+        let mut pane = TextPane::new(&lines);
+        pane.scroll_by(5);
+
+        let mut buffer = GridBuffer::<u16, 3, 2>::new();
+        pane.draw(&mut Canvas::new(&mut buffer));
+
+        assert_eq!(pane.scroll(), 3);
+        assert_eq!(testing::line_text(&buffer, 0), [b'c', b'c', 0x1e]);
+        assert_eq!(testing::line_text(&buffer, 1), [b'd', b'd', 0x1f]);
+    }
+
+    #[kernel_test]
+    fn test_text_pane_in_empty_window() {
+        let lines = ["aa", "bb"];
+        let pane = TextPane::new(&lines);
+
+        let mut buffer = GridBuffer::<u16, 4, 2>::new();
+        Window::new().draw_with(&mut Canvas::new(&mut buffer), |inner| {
+            pane.draw(inner);
+        });
+
+        assert_eq!(testing::line_text(&buffer, 0), [0xda, 0xc4, 0xc4, 0xbf]);
+        assert_eq!(testing::line_text(&buffer, 1), [0xc0, 0xc4, 0xc4, 0xd9]);
+    }
+
+    #[kernel_test]
+    fn test_text_pane_one_row() {
+        let lines = ["aa", "bb"];
+        let pane = TextPane::new(&lines);
+
+        let mut buffer = GridBuffer::<u16, 3, 1>::new();
+        pane.draw(&mut Canvas::new(&mut buffer));
+
+        assert_eq!(testing::line_text(&buffer, 0), [b'a', b'a', b' ']);
+    }
+}
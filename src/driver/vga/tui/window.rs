@@ -0,0 +1,104 @@
+use super::super::attribute;
+use super::super::color;
+use super::border::Border;
+use super::canvas::Canvas;
+use super::Widget;
+
+pub struct Window<'a> {
+    title: Option<&'a str>,
+    border: Border,
+    style: attribute::Attribute,
+}
+
+impl<'a> Window<'a> {
+    pub fn new() -> Self {
+        Self {
+            title: None,
+            border: Border::default(),
+            style: attribute::Attribute::default(),
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = Some(title);
+        self
+    }
+
+    pub fn border(mut self, border: Border) -> Self {
+        self.border = border;
+        self
+    }
+
+    pub fn style(mut self, background: color::Color, foreground: color::Color) -> Self {
+        self.style = attribute::Attribute::new(background, foreground);
+        self
+    }
+
+    pub fn draw_with<F>(&self, canvas: &mut Canvas, content: F)
+    where
+        F: FnOnce(&mut Canvas),
+    {
+        self.draw(canvas);
+
+        let mut inner = canvas.sub(canvas.bounds().inset(1));
+        inner.set_attribute(self.style);
+        content(&mut inner);
+    }
+}
+
+impl<'a> Default for Window<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Widget for Window<'a> {
+    fn draw(&self, canvas: &mut Canvas) {
+        let previous = canvas.style();
+        canvas.set_attribute(self.style);
+
+        canvas.clear();
+        canvas.frame(canvas.bounds(), self.border);
+
+        if let Some(title) = self.title {
+            if canvas.width() > 4 {
+                let mut header = canvas.sub(crate::libs::buffer::Region::new(
+                    (0, 1),
+                    (1, canvas.width() - 2),
+                ));
+                let written = header.write_str((0, 1), title);
+                header.put((0, 0), b' ');
+                header.put((0, written + 1), b' ');
+            }
+        }
+
+        canvas.set_attribute(previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::canvas::testing;
+    use super::*;
+    use crate::libs::buffer::GridBuffer;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_window_title() {
+        let mut buffer = GridBuffer::<u16, 8, 3>::new();
+        let mut canvas = Canvas::new(&mut buffer);
+
+        Window::new().title("ab").draw_with(&mut canvas, |inner| {
+            inner.write_str((0, 0), "xyz");
+        });
+
+        assert_eq!(
+            testing::line_text(&buffer, 0),
+            [0xda, b' ', b'a', b'b', b' ', 0xc4, 0xc4, 0xbf]
+        );
+        assert_eq!(
+            testing::line_text(&buffer, 1),
+            [0xb3, b'x', b'y', b'z', b' ', b' ', b' ', 0xb3]
+        );
+    }
+}
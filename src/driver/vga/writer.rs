@@ -2,9 +2,13 @@ use super::attribute;
 use super::buffer;
 use super::character;
 use super::color;
+use super::mode;
+use super::style;
+
+const BACKSPACE: u8 = 0x08;
 
 pub struct Writer {
-    buffer: &'static mut buffer::VGABuffer,
+    buffer: buffer::VGABuffer,
     position: (usize, usize),
     style: attribute::Attribute,
 }
@@ -20,7 +24,7 @@ impl Writer {
     }
 
     pub fn new_line(&mut self) {
-        if self.position.0 >= buffer::VGABuffer::HEIGHT - 1 {
+        if self.position.0 >= self.buffer.height() - 1 {
             self.buffer.move_up(1);
             self.position.1 = 0;
             return;
@@ -54,14 +58,63 @@ impl Writer {
         self.set_style(background, foreground)
     }
 
+    pub fn apply_style(&mut self, style: style::Style) {
+        self.style = style.attribute(mode::background_mode());
+    }
+
+    pub fn with_style<F, R>(&mut self, style: style::Style, function: F) -> R
+    where
+        F: FnOnce(&mut Writer) -> R,
+    {
+        let previous = self.replace_style(style);
+        let result = function(self);
+        self.restore_style(previous);
+        result
+    }
+
+    pub(super) fn replace_style(&mut self, style: style::Style) -> attribute::Attribute {
+        let previous = self.style;
+        self.apply_style(style);
+        previous
+    }
+
+    pub(super) fn restore_style(&mut self, style: attribute::Attribute) {
+        self.style = style;
+    }
+
     pub(super) fn new() -> Writer {
         Writer {
-            buffer: buffer::VGABuffer::get(),
+            buffer: buffer::VGABuffer::new(),
             position: (0, 0),
             style: attribute::Attribute::default(),
         }
     }
 
+    pub(super) fn resize(&mut self, width: usize, height: usize) {
+        if (width, height) == (self.buffer.width(), self.buffer.height()) {
+            self.buffer.sync();
+            return;
+        }
+
+        self.buffer.resize(width, height);
+        self.position = (0, 0);
+    }
+
+    pub(super) fn draw<const WIDTH: usize, const HEIGHT: usize>(
+        &mut self,
+        source: &crate::libs::buffer::GridBuffer<u16, WIDTH, HEIGHT>,
+    ) {
+        self.buffer.draw(source);
+    }
+
+    pub(super) fn buffer(&self) -> &buffer::VGABuffer {
+        &self.buffer
+    }
+
+    pub(super) fn dimensions(&self) -> (usize, usize) {
+        (self.buffer.width(), self.buffer.height())
+    }
+
     fn write_bytes(&mut self, bytes: &[u8]) {
         for k in 0..bytes.len() {
             self.write_byte(bytes[k]);
@@ -74,7 +127,12 @@ impl Writer {
             return;
         }
 
-        if self.position.1 >= buffer::VGABuffer::WIDTH {
+        if value == BACKSPACE {
+            self.position.1 = self.position.1.saturating_sub(1);
+            return;
+        }
+
+        if self.position.1 >= self.buffer.width() {
             self.new_line();
         }
 
@@ -96,9 +154,10 @@ impl core::fmt::Write for Writer {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, target_os = "none"))]
 mod tests {
     use super::*;
+    use crate::libs::testing::{kernel_bench, Bencher};
 
     impl Writer {
         pub(crate) fn assert_buffer_text_eq(&mut self, expected: buffer::VGABuffer) {
@@ -108,4 +167,27 @@ mod tests {
             assert_eq!(buffer, expected);
         }
     }
+
+    #[test_case]
+    fn test_with_style() {
+        let mut writer = super::super::VGA_WRITER.lock();
+        let previous = writer.style;
+
+        let style = style::Style::new()
+            .background(color::Color::Dim(color::ColorName::Red))
+            .foreground(color::Color::Dim(color::ColorName::Cyan));
+
+        let inner = writer.with_style(style, |writer| writer.style);
+
+        assert_eq!(inner.as_byte(), 0x43);
+        assert_eq!(writer.style, previous);
+    }
+
+    #[kernel_bench(tags(vga))]
+    fn bench_write_str(bencher: &mut Bencher) {
+        let mut writer = super::super::VGA_WRITER.lock();
+
+        bencher.iter(|| writer.write_str("The quick brown fox jumps over the lazy dog\n"));
+        writer.clear();
+    }
 }
@@ -1,15 +1,17 @@
-#![no_std]
-#![cfg_attr(test, no_main)]
+#![cfg_attr(target_os = "none", no_std)]
+#![cfg_attr(all(test, target_os = "none"), no_main)]
 #![feature(abi_x86_interrupt)]
-#![feature(custom_test_frameworks)]
-#![test_runner(crate::libs::testing::test_runner)]
-#![reexport_test_harness_main = "test_main"]
+#![cfg_attr(target_os = "none", feature(custom_test_frameworks))]
+#![cfg_attr(target_os = "none", test_runner(crate::libs::testing::test_runner))]
+#![cfg_attr(target_os = "none", reexport_test_harness_main = "test_main")]
+
+extern crate self as ferros;
 
 pub mod driver;
 pub mod libs;
 pub mod nucleus;
 
-#[cfg(test)]
+#[cfg(all(test, target_os = "none"))]
 #[no_mangle]
 pub extern "C" fn _start() -> ! {
     // init();
@@ -18,10 +20,11 @@ pub extern "C" fn _start() -> ! {
 }
 
 pub fn init() {
+    crate::libs::logger::init().ok();
     crate::nucleus::interrupt::init_idt();
 }
 
-#[cfg(test)]
+#[cfg(all(test, target_os = "none"))]
 #[panic_handler]
 fn panic(info: &core::panic::PanicInfo) -> ! {
     crate::libs::testing::panic(info)
@@ -29,7 +32,9 @@ fn panic(info: &core::panic::PanicInfo) -> ! {
 
 #[cfg(test)]
 mod tests {
-    #[test_case]
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
     fn test_run_lib() {
         assert!(true);
     }
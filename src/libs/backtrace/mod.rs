@@ -0,0 +1,236 @@
+mod symbols;
+
+pub use symbols::{embedded, Symbol, SymbolTable, SYMBOL_TABLE_SIZE};
+
+use core::fmt;
+
+pub const MAX_FRAMES: usize = 32;
+
+/// Largest distance between two frames that is still believed to be on the same stack.
+const MAX_FRAME_SIZE: u64 = 1024 * 1024;
+
+/// Walks the frame pointer chain. Each frame starts with the caller's saved frame pointer,
+/// followed by the return address into the caller.
+pub struct Frames {
+    frame_pointer: u64,
+    remaining: usize,
+    is_readable: fn(u64) -> bool,
+}
+
+impl Frames {
+    /// Starts at the frame of the function this is inlined into.
+    #[inline(always)]
+    pub fn current() -> Self {
+        let frame_pointer: u64;
+        unsafe { core::arch::asm!("mov {}, rbp", out(reg) frame_pointer, options(nomem, nostack)) };
+
+        unsafe { Self::from_frame_pointer(frame_pointer) }
+    }
+
+    /// # Safety
+    ///
+    /// `frame_pointer` must be the start of a readable frame chain, unless the walk is
+    /// `validated`. The walk stops at the first frame that is null, misaligned, or not above the
+    /// previous one.
+    pub unsafe fn from_frame_pointer(frame_pointer: u64) -> Self {
+        Self {
+            frame_pointer,
+            remaining: MAX_FRAMES,
+            is_readable: |_| true,
+        }
+    }
+
+    /// Stops the walk at the first frame `is_readable` rejects, for chains that may run into
+    /// unmapped memory. It is called with the address of each frame's two words.
+    pub fn validated(self, is_readable: fn(u64) -> bool) -> Self {
+        Self {
+            is_readable,
+            ..self
+        }
+    }
+}
+
+impl Iterator for Frames {
+    /// Return address.
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.remaining == 0 || self.frame_pointer == 0 || !self.frame_pointer.is_multiple_of(8) {
+            return None;
+        }
+
+        if !(self.is_readable)(self.frame_pointer) {
+            return None;
+        }
+
+        let frame = self.frame_pointer as *const u64;
+        let (caller, return_address) = unsafe { (frame.read(), frame.add(1).read()) };
+
+        if return_address == 0 {
+            return None;
+        }
+
+        self.remaining -= 1;
+        self.frame_pointer = match caller.checked_sub(self.frame_pointer) {
+            Some(distance) if distance > 0 && distance <= MAX_FRAME_SIZE => caller,
+            _ => 0,
+        };
+
+        Some(return_address)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Backtrace {
+    addresses: [u64; MAX_FRAMES],
+    len: usize,
+}
+
+impl Backtrace {
+    #[inline(always)]
+    pub fn capture() -> Self {
+        Self::from_frames(Frames::current())
+    }
+
+    pub fn from_frames(frames: Frames) -> Self {
+        let mut backtrace = Self {
+            addresses: [0; MAX_FRAMES],
+            len: 0,
+        };
+
+        for address in frames {
+            backtrace.addresses[backtrace.len] = address;
+            backtrace.len += 1;
+        }
+
+        backtrace
+    }
+
+    pub fn addresses(&self) -> &[u64] {
+        &self.addresses[..self.len]
+    }
+
+    /// Looks up the function of each return address. A return address can point just past the
+    /// end of its function, so the lookup uses the call instruction's last byte.
+    pub fn frames(&self) -> impl Iterator<Item = Frame> + '_ {
+        let table = embedded();
+
+        self.addresses().iter().map(move |address| Frame {
+            address: *address,
+            symbol: table
+                .and_then(|table| table.lookup(address - 1))
+                .map(|symbol| Symbol {
+                    offset: symbol.offset + 1,
+                    ..symbol
+                }),
+        })
+    }
+}
+
+impl fmt::Display for Backtrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "backtrace:")?;
+
+        for (index, frame) in self.frames().enumerate() {
+            writeln!(f, "{:>4}: {}", index, frame)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Frame {
+    pub address: u64,
+    pub symbol: Option<Symbol<'static>>,
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.symbol {
+            Some(symbol) => write!(f, "{:#018x} {}", self.address, symbol),
+            None => write!(f, "{:#018x} <unknown>", self.address),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    /// Builds a stack with frames at word offsets 0, 4 and 10, each returning to `0x1000 + index`.
+    fn stack(words: &mut [u64; 16]) -> u64 {
+        let base = words.as_ptr() as u64;
+
+        words[0] = base + 4 * 8;
+        words[1] = 0x1000;
+        words[4] = base + 10 * 8;
+        words[5] = 0x1001;
+        words[10] = 0;
+        words[11] = 0x1002;
+
+        base
+    }
+
+    #[kernel_test]
+    fn test_frames() {
+        let mut words = [0; 16];
+        let base = stack(&mut words);
+
+        let frames = unsafe { Frames::from_frame_pointer(base) };
+        let backtrace = Backtrace::from_frames(frames);
+
+        assert_eq!(backtrace.addresses(), &[0x1000, 0x1001, 0x1002]);
+    }
+
+    #[kernel_test]
+    fn test_frames_stop_at_bad_links() {
+        let mut words = [0; 16];
+        let base = stack(&mut words);
+
+        words[4] = base;
+        let frames = unsafe { Frames::from_frame_pointer(words.as_ptr() as u64) };
+        assert_eq!(frames.count(), 2);
+
+        words[4] = base + 10 * 8 + 1;
+        let frames = unsafe { Frames::from_frame_pointer(words.as_ptr() as u64) };
+        assert_eq!(frames.count(), 2);
+
+        words[5] = 0;
+        let frames = unsafe { Frames::from_frame_pointer(words.as_ptr() as u64) };
+        assert_eq!(frames.count(), 1);
+
+        assert_eq!(unsafe { Frames::from_frame_pointer(0) }.count(), 0);
+    }
+
+    #[kernel_test]
+    fn test_frames_validated() {
+        use core::sync::atomic::{AtomicU64, Ordering};
+
+        static UNREADABLE: AtomicU64 = AtomicU64::new(0);
+
+        let mut words = [0; 16];
+        let base = stack(&mut words);
+
+        UNREADABLE.store(base + 10 * 8, Ordering::SeqCst);
+        let frames = unsafe { Frames::from_frame_pointer(base) }
+            .validated(|frame| frame != UNREADABLE.load(Ordering::SeqCst));
+
+        assert_eq!(frames.count(), 2);
+    }
+
+    #[kernel_test]
+    fn test_frames_limit() {
+        let mut words = [0u64; 2 * (MAX_FRAMES + 8)];
+        let base = words.as_ptr() as u64;
+
+        for frame in 0..MAX_FRAMES + 8 {
+            words[2 * frame] = base + 16 * (frame as u64 + 1);
+            words[2 * frame + 1] = 0x1000 + frame as u64;
+        }
+
+        let frames = unsafe { Frames::from_frame_pointer(base) };
+        assert_eq!(frames.count(), MAX_FRAMES);
+    }
+}
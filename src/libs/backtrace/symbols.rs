@@ -0,0 +1,132 @@
+use core::cell::UnsafeCell;
+use core::fmt;
+
+/// First line of a table written by `scripts/embed-symbols.sh`.
+pub const HEADER: &str = "ferros symbols\n";
+pub const SYMBOL_TABLE_SIZE: usize = 512 * 1024;
+
+const PLACEHOLDER: &[u8] = b"no symbols\n";
+
+/// Space for the symbol table, filled in after linking. Interior mutability keeps the compiler
+/// from folding reads to the placeholder it was compiled with.
+#[repr(transparent)]
+struct Reserved(UnsafeCell<[u8; SYMBOL_TABLE_SIZE]>);
+
+unsafe impl Sync for Reserved {}
+
+#[used]
+#[cfg_attr(target_os = "none", link_section = ".symbols")]
+static RESERVED: Reserved = Reserved(UnsafeCell::new(placeholder()));
+
+/// Non-zero contents keep the section from becoming `NOBITS`, which takes no space in the file
+/// for the post-link step to overwrite.
+const fn placeholder() -> [u8; SYMBOL_TABLE_SIZE] {
+    let mut bytes = [0; SYMBOL_TABLE_SIZE];
+    let mut index = 0;
+
+    while index < PLACEHOLDER.len() {
+        bytes[index] = PLACEHOLDER[index];
+        index += 1;
+    }
+
+    bytes
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol<'a> {
+    pub name: &'a str,
+    pub address: u64,
+    pub offset: u64,
+}
+
+impl fmt::Display for Symbol<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}+{:#x}", self.name, self.offset)
+    }
+}
+
+/// Function start addresses and names, one `address name` line each, sorted by address. The
+/// address is hexadecimal without a prefix.
+#[derive(Clone, Copy)]
+pub struct SymbolTable<'a> {
+    entries: &'a str,
+}
+
+impl<'a> SymbolTable<'a> {
+    /// Reads a table up to its terminating NUL, or returns `None` if it has no header.
+    pub fn parse(bytes: &'a [u8]) -> Option<Self> {
+        let length = bytes
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(bytes.len());
+        let text = core::str::from_utf8(&bytes[..length]).ok()?;
+
+        text.strip_prefix(HEADER).map(|entries| Self { entries })
+    }
+
+    pub fn symbols(&self) -> impl Iterator<Item = (u64, &'a str)> {
+        self.entries.lines().filter_map(|line| {
+            let (address, name) = line.split_once(' ')?;
+            Some((u64::from_str_radix(address, 16).ok()?, name))
+        })
+    }
+
+    /// Finds the function containing `address`: the last symbol starting at or before it.
+    pub fn lookup(&self, address: u64) -> Option<Symbol<'a>> {
+        self.symbols()
+            .take_while(|(start, _)| *start <= address)
+            .last()
+            .map(|(start, name)| Symbol {
+                name,
+                address: start,
+                offset: address - start,
+            })
+    }
+}
+
+/// The table embedded in this kernel, if the post-link step has run.
+pub fn embedded() -> Option<SymbolTable<'static>> {
+    SymbolTable::parse(unsafe { &*RESERVED.0.get() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::format::FixedString;
+    use crate::libs::testing::kernel_test;
+    use core::fmt::Write;
+
+    const TABLE: &[u8] =
+        b"ferros symbols\n201000 _start\n201040 ferros::main\n2010a0 <T as ferros::Testable>::run\n\0\0";
+
+    #[kernel_test]
+    fn test_parse() {
+        assert!(SymbolTable::parse(TABLE).is_some());
+        assert!(SymbolTable::parse(&[0; 16]).is_none());
+        assert!(SymbolTable::parse(b"201000 _start\n").is_none());
+
+        let table = SymbolTable::parse(b"ferros symbols\n").unwrap();
+        assert_eq!(table.symbols().count(), 0);
+        assert_eq!(table.lookup(0x1000), None);
+    }
+
+    #[kernel_test]
+    fn test_lookup() {
+        let table = SymbolTable::parse(TABLE).unwrap();
+
+        assert_eq!(table.lookup(0x200fff), None);
+        assert_eq!(
+            table.lookup(0x201000),
+            Some(Symbol {
+                name: "_start",
+                address: 0x201000,
+                offset: 0,
+            })
+        );
+        assert_eq!(table.lookup(0x20105f).unwrap().name, "ferros::main");
+
+        let mut text = FixedString::<64>::new();
+        write!(text, "{}", table.lookup(0x2010b4).unwrap()).unwrap();
+        assert_eq!(text.as_str(), "<T as ferros::Testable>::run+0x14");
+    }
+}
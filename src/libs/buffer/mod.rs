@@ -1,3 +1,11 @@
+mod region;
+mod ring;
+mod view;
+
+pub use region::Region;
+pub use ring::RingBuffer;
+pub use view::GridView;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct GridBuffer<T, const WIDTH: usize, const HEIGHT: usize> {
     pub content: [[T; WIDTH]; HEIGHT],
@@ -19,6 +27,59 @@ impl<T, const WIDTH: usize, const HEIGHT: usize> GridBuffer<T, WIDTH, HEIGHT> {
     {
         Self { content: array }
     }
+
+    pub const fn region(&self) -> Region {
+        Region::full(HEIGHT, WIDTH)
+    }
+
+    pub fn view(&self, region: Region) -> GridView<'_, T, WIDTH, HEIGHT> {
+        GridView::new(self, region)
+    }
+
+    pub fn rows(&self) -> core::slice::Iter<'_, [T; WIDTH]> {
+        self.content.iter()
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.content.iter().enumerate().flat_map(|(line, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(column, value)| ((line, column), value))
+        })
+    }
+
+    pub fn fill(&mut self, region: Region, value: T)
+    where
+        T: Copy,
+    {
+        let region = region.clip(HEIGHT, WIDTH);
+
+        for line_pos in region.lines() {
+            for char_pos in region.columns() {
+                self.content[line_pos][char_pos] = value;
+            }
+        }
+    }
+
+    pub fn blit<const SOURCE_WIDTH: usize, const SOURCE_HEIGHT: usize>(
+        &mut self,
+        source: &GridBuffer<T, SOURCE_WIDTH, SOURCE_HEIGHT>,
+        source_region: Region,
+        destination: (usize, usize),
+    ) where
+        T: Copy,
+    {
+        let source_region = source_region.clip(SOURCE_HEIGHT, SOURCE_WIDTH);
+        let destination_region = Region::new(destination, source_region.size()).clip(HEIGHT, WIDTH);
+
+        for line_offset in 0..destination_region.height {
+            for char_offset in 0..destination_region.width {
+                self.content[destination_region.line + line_offset]
+                    [destination_region.column + char_offset] = source.content
+                    [source_region.line + line_offset][source_region.column + char_offset];
+            }
+        }
+    }
 }
 
 impl<T, const WIDTH: usize, const HEIGHT: usize> GridBuffer<T, WIDTH, HEIGHT>
@@ -41,6 +102,42 @@ where
         self.shift_vertical(vertical);
     }
 
+    pub fn shift_region(&mut self, region: Region, count: (isize, isize)) {
+        let region = region.clip(HEIGHT, WIDTH);
+        let (horizontal, vertical) = count;
+        let horizontal = Self::clip_shift(horizontal, region.width);
+        let vertical = Self::clip_shift(vertical, region.height);
+
+        let lines = region.lines();
+        let columns = region.columns();
+
+        for line_index in 0..region.height {
+            let line_pos = match vertical > 0 {
+                true => lines.end - 1 - line_index,
+                false => lines.start + line_index,
+            };
+
+            for char_index in 0..region.width {
+                let char_pos = match horizontal > 0 {
+                    true => columns.end - 1 - char_index,
+                    false => columns.start + char_index,
+                };
+
+                let source_line = Self::shift_pos(line_pos as isize, vertical);
+                let source_char = Self::shift_pos(char_pos as isize, horizontal);
+
+                let inside = source_line >= 0
+                    && source_char >= 0
+                    && region.contains((source_line as usize, source_char as usize));
+
+                self.content[line_pos][char_pos] = match inside {
+                    true => self.content[source_line as usize][source_char as usize],
+                    false => T::default(),
+                };
+            }
+        }
+    }
+
     fn shift_vertical(&mut self, count: isize) {
         let (left, start, end, right) = Self::get_shift_ranges(count, HEIGHT);
         let sign = (((left >= 0) && (start >= 0) && (end >= 0) && (right >= 0)) as isize) * 2 - 1;
@@ -129,8 +226,9 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::libs::testing::{kernel_bench, kernel_test, property, Bencher};
 
-    #[test_case]
+    #[kernel_test]
     fn test_shift() {
         let mut buffer = GridBuffer::<u8, 5, 4>::from_array([
             [1, 2, 3, 4, 5],
@@ -152,7 +250,7 @@ mod tests {
         );
     }
 
-    #[test_case]
+    #[kernel_test]
     fn test_shift_vertical() {
         let mut buffer = GridBuffer::<u8, 5, 4>::from_array([
             [1, 2, 3, 4, 5],
@@ -234,7 +332,7 @@ mod tests {
         assert_eq!(buffer, GridBuffer::<u8, 5, 4>::new_with_default(0));
     }
 
-    #[test_case]
+    #[kernel_test]
     fn test_shift_horizontal() {
         let mut buffer = GridBuffer::<u8, 5, 4>::from_array([
             [1, 2, 3, 4, 5],
@@ -316,7 +414,7 @@ mod tests {
         assert_eq!(buffer, GridBuffer::<u8, 5, 4>::new_with_default(0));
     }
 
-    #[test_case]
+    #[kernel_test]
     fn test_get_shift_ends() {
         assert_eq!(
             GridBuffer::<u8, 0, 0>::get_shift_ranges(5, 10),
@@ -336,7 +434,7 @@ mod tests {
         );
     }
 
-    #[test_case]
+    #[kernel_test]
     fn test_get_source_pos() {
         assert_eq!(
             GridBuffer::<u8, 0, 0>::get_source_pos((10, 20), (3, 5)),
@@ -360,7 +458,7 @@ mod tests {
         );
     }
 
-    #[test_case]
+    #[kernel_test]
     fn test_get_source_pos__odd() {
         assert_eq!(
             GridBuffer::<u8, 0, 0>::get_source_pos(
@@ -392,7 +490,7 @@ mod tests {
         );
     }
 
-    #[test_case]
+    #[kernel_test]
     fn test_clip_shift() {
         assert_eq!(GridBuffer::<u8, 0, 0>::clip_shift(5, 10), 5);
         assert_eq!(GridBuffer::<u8, 0, 0>::clip_shift(-5, 10), -5);
@@ -400,7 +498,7 @@ mod tests {
         assert_eq!(GridBuffer::<u8, 0, 0>::clip_shift(-15, 10), -10);
     }
 
-    #[test_case]
+    #[kernel_test]
     fn test_clip_shift__idempotent() {
         assert_eq!(
             GridBuffer::<u8, 0, 0>::clip_shift(GridBuffer::<u8, 0, 0>::clip_shift(5, 10), 10),
@@ -420,14 +518,14 @@ mod tests {
         );
     }
 
-    #[test_case]
+    #[kernel_test]
     fn test_shift_pos() {
         assert_eq!(GridBuffer::<u8, 0, 0>::shift_pos(5, 2), 3);
         assert_eq!(GridBuffer::<u8, 0, 0>::shift_pos(5, -2), 7);
         assert_eq!(GridBuffer::<u8, 0, 0>::shift_pos(5, 0), 5);
     }
 
-    #[test_case]
+    #[kernel_test]
     fn test_shift_pos__odd() {
         assert_eq!(
             GridBuffer::<u8, 0, 0>::shift_pos(GridBuffer::<u8, 0, 0>::shift_pos(5, 2), -2),
@@ -438,4 +536,186 @@ mod tests {
             5
         );
     }
+
+    #[kernel_test]
+    fn test_clip_shift__property() {
+        property::check(&(-64..64isize, 0..32usize), |(count, bound)| {
+            let clipped = GridBuffer::<u8, 0, 0>::clip_shift(count, bound);
+
+            clipped.unsigned_abs() <= bound
+                && GridBuffer::<u8, 0, 0>::clip_shift(clipped, bound) == clipped
+                && (count.unsigned_abs() > bound || clipped == count)
+        });
+    }
+
+    #[kernel_test]
+    fn test_shift_pos__property() {
+        property::check(&(-1000..1000isize, -1000..1000isize), |(pos, count)| {
+            let shifted = GridBuffer::<u8, 0, 0>::shift_pos(pos, count);
+
+            GridBuffer::<u8, 0, 0>::shift_pos(shifted, -count) == pos && shifted - pos == -count
+        });
+    }
+
+    #[kernel_test]
+    fn test_get_shift_ranges__property() {
+        property::check(&(-64..64isize, 0..32usize), |(count, bound)| {
+            let (left, start, end, right) = GridBuffer::<u8, 0, 0>::get_shift_ranges(count, bound);
+            let copied = bound - GridBuffer::<u8, 0, 0>::clip_shift(count, bound).unsigned_abs();
+
+            left <= start
+                && start <= end
+                && end <= right
+                && right - left == bound as isize
+                && (end - start) as usize == copied
+        });
+    }
+
+    #[kernel_test]
+    fn test_shift__property() {
+        let strategy = (property::grid::<_, 6, 5>(0..=255u8), -8..8isize, -8..8isize);
+
+        property::check(&strategy, |(buffer, horizontal, vertical)| {
+            let mut expected = GridBuffer::<u8, 6, 5>::new();
+
+            for ((line, column), cell) in buffer.cells() {
+                let target = (line as isize + vertical, column as isize + horizontal);
+
+                if (0..5).contains(&target.0) && (0..6).contains(&target.1) {
+                    expected.content[target.0 as usize][target.1 as usize] = *cell;
+                }
+            }
+
+            let mut shifted = buffer;
+            shifted.shift((horizontal, vertical));
+            shifted == expected
+        });
+    }
+
+    #[kernel_test]
+    fn test_fill() {
+        let mut buffer = GridBuffer::<u8, 5, 4>::new();
+
+        buffer.fill(Region::new((1, 3), (5, 5)), 7);
+
+        assert_eq!(
+            buffer,
+            GridBuffer::<u8, 5, 4>::from_array([
+                [0, 0, 0, 0, 0],
+                [0, 0, 0, 7, 7],
+                [0, 0, 0, 7, 7],
+                [0, 0, 0, 7, 7],
+            ])
+        );
+    }
+
+    #[kernel_test]
+    fn test_blit() {
+        let source = GridBuffer::<u8, 3, 2>::from_array([[1, 2, 3], [4, 5, 6]]);
+        let mut buffer = GridBuffer::<u8, 5, 4>::new();
+
+        buffer.blit(&source, Region::new((0, 1), (2, 2)), (1, 0));
+        buffer.blit(&source, source.region(), (3, 3));
+
+        assert_eq!(
+            buffer,
+            GridBuffer::<u8, 5, 4>::from_array([
+                [0, 0, 0, 0, 0],
+                [2, 3, 0, 0, 0],
+                [5, 6, 0, 0, 0],
+                [0, 0, 0, 1, 2],
+            ])
+        );
+    }
+
+    #[kernel_test]
+    fn test_shift_region() {
+        let mut buffer = GridBuffer::<u8, 5, 4>::from_array([
+            [1, 2, 3, 4, 5],
+            [6, 7, 8, 9, 10],
+            [11, 12, 13, 14, 15],
+            [16, 17, 18, 19, 20],
+        ]);
+
+        buffer.shift_region(Region::new((1, 1), (3, 3)), (0, -1));
+
+        assert_eq!(
+            buffer,
+            GridBuffer::<u8, 5, 4>::from_array([
+                [1, 2, 3, 4, 5],
+                [6, 12, 13, 14, 10],
+                [11, 17, 18, 19, 15],
+                [16, 0, 0, 0, 20],
+            ])
+        );
+
+        buffer.shift_region(Region::new((0, 0), (2, 5)), (2, 1));
+
+        assert_eq!(
+            buffer,
+            GridBuffer::<u8, 5, 4>::from_array([
+                [0, 0, 0, 0, 0],
+                [0, 0, 1, 2, 3],
+                [11, 17, 18, 19, 15],
+                [16, 0, 0, 0, 20],
+            ])
+        );
+    }
+
+    #[kernel_test]
+    fn test_shift_region__extreme_counts() {
+        let mut buffer = GridBuffer::<u8, 3, 3>::from_array([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+
+        buffer.shift_region(Region::new((0, 0), (2, 2)), (isize::MIN, 0));
+        buffer.shift_region(Region::new((1, 1), (2, 2)), (0, isize::MAX));
+
+        assert_eq!(
+            buffer,
+            GridBuffer::<u8, 3, 3>::from_array([[0, 0, 3], [0, 0, 0], [7, 0, 0]])
+        );
+    }
+
+    #[kernel_test]
+    fn test_view() {
+        let buffer = GridBuffer::<u8, 5, 4>::from_array([
+            [1, 2, 3, 4, 5],
+            [6, 7, 8, 9, 10],
+            [11, 12, 13, 14, 15],
+            [16, 17, 18, 19, 20],
+        ]);
+
+        let view = buffer.view(Region::new((2, 3), (5, 5)));
+
+        assert_eq!(view.region(), Region::new((2, 3), (2, 2)));
+        assert_eq!(view.get((1, 0)), Some(&19));
+        assert_eq!(view.get((2, 0)), None);
+
+        let mut rows = view.rows();
+        assert_eq!(rows.next(), Some(&[14, 15][..]));
+        assert_eq!(rows.next(), Some(&[19, 20][..]));
+        assert_eq!(rows.next(), None);
+
+        let mut cells = view.cells();
+        assert_eq!(cells.next(), Some(((0, 0), &14)));
+        assert_eq!(cells.nth(2), Some(((1, 1), &20)));
+    }
+
+    #[kernel_test]
+    fn test_cells() {
+        let buffer = GridBuffer::<u8, 2, 2>::from_array([[1, 2], [3, 4]]);
+
+        let mut cells = buffer.cells();
+        assert_eq!(cells.next(), Some(((0, 0), &1)));
+        assert_eq!(cells.next(), Some(((0, 1), &2)));
+        assert_eq!(cells.next(), Some(((1, 0), &3)));
+        assert_eq!(cells.next(), Some(((1, 1), &4)));
+        assert_eq!(cells.next(), None);
+    }
+
+    #[kernel_bench]
+    fn bench_shift(bencher: &mut Bencher) {
+        let mut buffer = GridBuffer::<u16, 80, 25>::new_with_default(0x0f20);
+
+        bencher.iter(|| buffer.shift((1, -1)));
+    }
 }
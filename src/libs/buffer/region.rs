@@ -0,0 +1,136 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Region {
+    pub line: usize,
+    pub column: usize,
+    pub height: usize,
+    pub width: usize,
+}
+
+impl Region {
+    pub const fn new(position: (usize, usize), size: (usize, usize)) -> Self {
+        Self {
+            line: position.0,
+            column: position.1,
+            height: size.0,
+            width: size.1,
+        }
+    }
+
+    /// Takes the size in the same `(height, width)` order as `new`.
+    pub const fn full(height: usize, width: usize) -> Self {
+        Self::new((0, 0), (height, width))
+    }
+
+    pub const fn position(&self) -> (usize, usize) {
+        (self.line, self.column)
+    }
+
+    pub const fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    pub const fn end(&self) -> (usize, usize) {
+        (self.line + self.height, self.column + self.width)
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.height == 0 || self.width == 0
+    }
+
+    pub const fn contains(&self, position: (usize, usize)) -> bool {
+        let (line, column) = position;
+        let (end_line, end_column) = self.end();
+
+        line >= self.line && line < end_line && column >= self.column && column < end_column
+    }
+
+    pub fn intersect(&self, other: &Region) -> Region {
+        let line = core::cmp::max(self.line, other.line);
+        let column = core::cmp::max(self.column, other.column);
+        let end_line = core::cmp::min(self.end().0, other.end().0);
+        let end_column = core::cmp::min(self.end().1, other.end().1);
+
+        Region::new(
+            (line, column),
+            (
+                end_line.saturating_sub(line),
+                end_column.saturating_sub(column),
+            ),
+        )
+    }
+
+    pub fn clip(&self, height: usize, width: usize) -> Region {
+        self.intersect(&Region::full(height, width))
+    }
+
+    pub fn inset(&self, amount: usize) -> Region {
+        Region::new(
+            (self.line + amount, self.column + amount),
+            (
+                self.height.saturating_sub(2 * amount),
+                self.width.saturating_sub(2 * amount),
+            ),
+        )
+    }
+
+    pub fn lines(&self) -> core::ops::Range<usize> {
+        self.line..self.end().0
+    }
+
+    pub fn columns(&self) -> core::ops::Range<usize> {
+        self.column..self.end().1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_intersect() {
+        let region = Region::new((1, 2), (3, 4));
+
+        assert_eq!(
+            region.intersect(&Region::new((2, 0), (10, 4))),
+            Region::new((2, 2), (2, 2))
+        );
+        assert_eq!(
+            region.intersect(&Region::new((10, 10), (2, 2))),
+            Region::new((10, 10), (0, 0))
+        );
+        assert!(region.intersect(&Region::new((10, 10), (2, 2))).is_empty());
+    }
+
+    #[kernel_test]
+    fn test_clip() {
+        assert_eq!(
+            Region::new((3, 3), (5, 5)).clip(4, 5),
+            Region::new((3, 3), (1, 2))
+        );
+        assert_eq!(
+            Region::new((0, 0), (2, 2)).clip(4, 5),
+            Region::new((0, 0), (2, 2))
+        );
+    }
+
+    #[kernel_test]
+    fn test_contains() {
+        let region = Region::new((1, 2), (3, 4));
+
+        assert!(region.contains((1, 2)));
+        assert!(region.contains((3, 5)));
+        assert!(!region.contains((4, 5)));
+        assert!(!region.contains((3, 6)));
+        assert!(!region.contains((0, 2)));
+    }
+
+    #[kernel_test]
+    fn test_inset() {
+        assert_eq!(
+            Region::new((1, 2), (5, 6)).inset(1),
+            Region::new((2, 3), (3, 4))
+        );
+        assert!(Region::new((1, 2), (1, 6)).inset(1).is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+#[derive(Debug, Clone, Copy)]
+pub struct RingBuffer<T, const SIZE: usize> {
+    content: [T; SIZE],
+    start: usize,
+    length: usize,
+}
+
+impl<T, const SIZE: usize> RingBuffer<T, SIZE>
+where
+    T: Copy,
+{
+    pub const fn new_with_default(default: T) -> Self {
+        Self {
+            content: [default; SIZE],
+            start: 0,
+            length: 0,
+        }
+    }
+
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.length == SIZE
+    }
+
+    pub const fn capacity(&self) -> usize {
+        SIZE
+    }
+
+    /// Appends a value, handing it back when the buffer is full.
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+
+        self.content[(self.start + self.length) % SIZE] = value;
+        self.length += 1;
+        Ok(())
+    }
+
+    /// Appends a value, evicting and returning the oldest one when the buffer is full.
+    pub fn push_overwrite(&mut self, value: T) -> Option<T> {
+        if SIZE == 0 {
+            return Some(value);
+        }
+
+        let evicted = match self.is_full() {
+            true => self.pop(),
+            false => None,
+        };
+
+        self.content[(self.start + self.length) % SIZE] = value;
+        self.length += 1;
+        evicted
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let value = self.content[self.start];
+        self.start = (self.start + 1) % SIZE;
+        self.length -= 1;
+        Some(value)
+    }
+
+    pub fn clear(&mut self) {
+        self.start = 0;
+        self.length = 0;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        (0..self.length).map(move |index| &self.content[(self.start + index) % SIZE])
+    }
+}
+
+impl<T, const SIZE: usize> RingBuffer<T, SIZE>
+where
+    T: Default + Copy,
+{
+    pub fn new() -> Self {
+        Self::new_with_default(T::default())
+    }
+}
+
+impl<T, const SIZE: usize> Default for RingBuffer<T, SIZE>
+where
+    T: Default + Copy,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_push_pop() {
+        let mut ring = RingBuffer::<u8, 3>::new();
+
+        assert_eq!(ring.pop(), None);
+        assert_eq!(ring.push(1), Ok(()));
+        assert_eq!(ring.push(2), Ok(()));
+        assert_eq!(ring.push(3), Ok(()));
+        assert!(ring.is_full());
+        assert_eq!(ring.push(4), Err(4));
+
+        assert_eq!(ring.pop(), Some(1));
+        assert_eq!(ring.push(5), Ok(()));
+        assert!(ring.iter().copied().eq([2, 3, 5]));
+        assert_eq!(ring.len(), 3);
+    }
+
+    #[kernel_test]
+    fn test_push_overwrite() {
+        let mut ring = RingBuffer::<u8, 2>::new();
+
+        assert_eq!(ring.push_overwrite(1), None);
+        assert_eq!(ring.push_overwrite(2), None);
+        assert_eq!(ring.push_overwrite(3), Some(1));
+        assert!(ring.iter().copied().eq([2, 3]));
+
+        ring.clear();
+        assert!(ring.is_empty());
+        assert_eq!(RingBuffer::<u8, 0>::new().push_overwrite(7), Some(7));
+    }
+}
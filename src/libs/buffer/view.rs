@@ -0,0 +1,55 @@
+use super::region::Region;
+use super::GridBuffer;
+
+pub struct GridView<'a, T, const WIDTH: usize, const HEIGHT: usize> {
+    buffer: &'a GridBuffer<T, WIDTH, HEIGHT>,
+    region: Region,
+}
+
+impl<'a, T, const WIDTH: usize, const HEIGHT: usize> GridView<'a, T, WIDTH, HEIGHT> {
+    pub(super) fn new(buffer: &'a GridBuffer<T, WIDTH, HEIGHT>, region: Region) -> Self {
+        Self {
+            buffer,
+            region: region.clip(HEIGHT, WIDTH),
+        }
+    }
+
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    pub fn width(&self) -> usize {
+        self.region.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.region.height
+    }
+
+    pub fn get(&self, position: (usize, usize)) -> Option<&'a T> {
+        let (line, column) = position;
+
+        if line >= self.region.height || column >= self.region.width {
+            return None;
+        }
+
+        Some(&self.buffer.content[self.region.line + line][self.region.column + column])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + 'a {
+        let buffer = self.buffer;
+        let columns = self.region.columns();
+
+        self.region
+            .lines()
+            .map(move |line| &buffer.content[line][columns.clone()])
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &'a T)> + 'a {
+        self.rows().enumerate().flat_map(|(line, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(column, value)| ((line, column), value))
+        })
+    }
+}
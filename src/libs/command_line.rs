@@ -0,0 +1,41 @@
+const COMMAND_LINE_FILE: &str = "opt/ferros/cmdline";
+pub const COMMAND_LINE_SIZE: usize = 256;
+
+/// Reads the kernel command line that `scripts/runner.sh` passes through QEMU's fw_cfg.
+pub fn read(buffer: &mut [u8; COMMAND_LINE_SIZE]) -> &str {
+    let length = crate::driver::fw_cfg::read_file(COMMAND_LINE_FILE, buffer).unwrap_or(0);
+
+    match core::str::from_utf8(&buffer[..length]) {
+        Ok(command_line) => command_line.trim_end_matches('\0'),
+        Err(_) => "",
+    }
+}
+
+/// Returns the value of the last `key=value` argument.
+pub fn value<'a>(command_line: &'a str, key: &str) -> Option<&'a str> {
+    command_line
+        .split_whitespace()
+        .filter_map(|argument| argument.split_once('='))
+        .filter(|(name, _)| *name == key)
+        .map(|(_, value)| value)
+        .next_back()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_value() {
+        let command_line = "buffer log=info,ferros::driver=debug seed=1 seed=2 --exact";
+
+        assert_eq!(
+            value(command_line, "log"),
+            Some("info,ferros::driver=debug")
+        );
+        assert_eq!(value(command_line, "seed"), Some("2"));
+        assert_eq!(value(command_line, "tag"), None);
+        assert_eq!(value("", "log"), None);
+    }
+}
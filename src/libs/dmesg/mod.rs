@@ -0,0 +1,208 @@
+mod ring;
+
+pub use ring::{Ring, RING_SIZE};
+
+use core::fmt;
+use core::ops::Range;
+use core::sync::atomic::{AtomicPtr, AtomicU64, Ordering};
+
+const MAGIC: u64 = u64::from_be_bytes(*b"FEROSLOG");
+const PAGE_SIZE: u64 = 4096;
+pub const PERSISTENT_SIZE: u64 =
+    (core::mem::size_of::<Persistent>() as u64 + PAGE_SIZE - 1) & !(PAGE_SIZE - 1);
+
+static EARLY: Ring = Ring::new();
+static ACTIVE: AtomicPtr<Ring> = AtomicPtr::new(&EARLY as *const Ring as *mut Ring);
+static PREVIOUS: AtomicPtr<Ring> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Layout of the region kept across soft reboots. Each boot logs into the ring the previous boot
+/// did not use, so the last boot's messages stay readable.
+#[repr(C)]
+pub struct Persistent {
+    magic: AtomicU64,
+    boots: AtomicU64,
+    rings: [Ring; 2],
+}
+
+impl Default for Persistent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Persistent {
+    pub const fn new() -> Self {
+        Self {
+            magic: AtomicU64::new(0),
+            boots: AtomicU64::new(0),
+            rings: [Ring::new(), Ring::new()],
+        }
+    }
+
+    /// Returns the ring for this boot, cleared, and the previous boot's ring if the region held a
+    /// valid log.
+    fn start_boot(&self) -> (&Ring, Option<&Ring>) {
+        let valid = self.magic.load(Ordering::SeqCst) == MAGIC;
+
+        let boots = match valid {
+            true => self.boots.fetch_add(1, Ordering::SeqCst) + 1,
+            false => {
+                self.magic.store(MAGIC, Ordering::SeqCst);
+                self.boots.store(0, Ordering::SeqCst);
+                0
+            }
+        };
+
+        let current = &self.rings[(boots % 2) as usize];
+        let previous = &self.rings[((boots + 1) % 2) as usize];
+        current.clear();
+
+        (current, if valid { Some(previous) } else { None })
+    }
+
+    pub fn boots(&self) -> u64 {
+        self.boots.load(Ordering::SeqCst)
+    }
+}
+
+/// Moves the log into persistent memory, carrying over what was written so far.
+///
+/// # Safety
+///
+/// `region` must be mapped, `PERSISTENT_SIZE` bytes long, at the same physical address on every
+/// boot, and never used for anything else.
+pub unsafe fn attach(region: *mut Persistent) {
+    let persistent = &*region;
+    let (current, previous) = persistent.start_boot();
+
+    EARLY.read(|bytes| current.write(bytes));
+
+    if let Some(previous) = previous {
+        PREVIOUS.store(previous as *const Ring as *mut Ring, Ordering::SeqCst);
+    }
+
+    ACTIVE.store(current as *const Ring as *mut Ring, Ordering::SeqCst);
+}
+
+/// Picks the persistent region at the top of the highest usable physical memory range, given as
+/// `(start, end)` addresses. The same memory map yields the same address on every boot.
+pub fn persistent_region<I>(usable: I) -> Option<u64>
+where
+    I: Iterator<Item = (u64, u64)>,
+{
+    let (start, end) = usable.max_by_key(|(_, end)| *end)?;
+    let base = end.checked_sub(PERSISTENT_SIZE)? & !(PAGE_SIZE - 1);
+
+    match base >= start {
+        true => Some(base),
+        false => None,
+    }
+}
+
+/// Attaches the persistent region chosen from the bootloader's memory map and returns its physical
+/// address range. Needs the bootloader's `map_physical_memory` feature. The region is taken from
+/// usable memory, so a frame allocator must leave the returned range out.
+pub fn init(boot_info: &'static bootloader::BootInfo) -> Option<Range<u64>> {
+    use bootloader::bootinfo::MemoryRegionType;
+
+    let usable = boot_info
+        .memory_map
+        .iter()
+        .filter(|region| region.region_type == MemoryRegionType::Usable)
+        .map(|region| (region.range.start_addr(), region.range.end_addr()));
+
+    let base = persistent_region(usable)?;
+    let region = (boot_info.physical_memory_offset + base) as *mut Persistent;
+    unsafe { attach(region) };
+
+    Some(base..base + PERSISTENT_SIZE)
+}
+
+fn active() -> &'static Ring {
+    unsafe { &*ACTIVE.load(Ordering::SeqCst) }
+}
+
+pub fn write(bytes: &[u8]) {
+    active().write(bytes);
+}
+
+pub fn print(args: fmt::Arguments) {
+    fmt::Write::write_fmt(&mut active(), args).ok();
+}
+
+/// Writes this boot's messages, oldest first.
+pub fn read(out: &mut dyn fmt::Write) -> fmt::Result {
+    active().write_to(out)
+}
+
+/// Calls `function` with this boot's raw bytes, oldest first, in chunks.
+pub fn read_bytes<F>(function: F)
+where
+    F: FnMut(&[u8]),
+{
+    active().read(function)
+}
+
+/// Writes the previous boot's messages, if they survived the reboot.
+pub fn read_previous(out: &mut dyn fmt::Write) -> Option<fmt::Result> {
+    let previous = PREVIOUS.load(Ordering::SeqCst);
+
+    match previous.is_null() {
+        true => None,
+        false => Some(unsafe { &*previous }.write_to(out)),
+    }
+}
+
+struct SerialConsole;
+
+impl fmt::Write for SerialConsole {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        crate::serial_print!("{}", string);
+        Ok(())
+    }
+}
+
+/// Dumps this boot's messages to the serial console, for panic handlers.
+pub fn dump_to_serial() {
+    crate::serial_println!("--- dmesg ---");
+    read(&mut SerialConsole).ok();
+    crate::serial_println!("--- end dmesg ---");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_start_boot() {
+        static REGION: Persistent = Persistent::new();
+
+        let (first, previous) = REGION.start_boot();
+        assert!(previous.is_none());
+        first.write(b"first boot\n");
+
+        let (second, previous) = REGION.start_boot();
+        assert_eq!(REGION.boots(), 1);
+        assert_eq!(second.written(), 0);
+        assert_eq!(previous.map(Ring::written), Some(11));
+
+        let (third, previous) = REGION.start_boot();
+        assert!(core::ptr::eq(third, first));
+        assert!(core::ptr::eq(previous.unwrap(), second));
+        assert_eq!(third.written(), 0);
+    }
+
+    #[kernel_test]
+    fn test_persistent_region() {
+        let regions = [(0x10_0000, 0x20_0000), (0x40_0000, 0x7fe_0000)];
+
+        assert_eq!(
+            persistent_region(regions.iter().copied()),
+            Some((0x7fe_0000 - PERSISTENT_SIZE) & !0xfff)
+        );
+        assert_eq!(persistent_region([(0x1000, 0x2000)].iter().copied()), None);
+        assert_eq!(persistent_region(core::iter::empty()), None);
+        assert_eq!(PERSISTENT_SIZE % PAGE_SIZE, 0);
+    }
+}
@@ -0,0 +1,217 @@
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+pub const RING_SIZE: usize = 32 * 1024;
+
+const CHUNK_SIZE: usize = 256;
+
+/// A byte ring that writers append to without locking: each write reserves its range by advancing
+/// `head`, so concurrent writers never share bytes. Once the ring has wrapped, readers skip the
+/// partially overwritten oldest line.
+#[repr(C)]
+pub struct Ring {
+    head: AtomicU64,
+    data: UnsafeCell<[u8; RING_SIZE]>,
+}
+
+unsafe impl Sync for Ring {}
+
+impl Default for Ring {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Ring {
+    pub const fn new() -> Self {
+        Self {
+            head: AtomicU64::new(0),
+            data: UnsafeCell::new([0; RING_SIZE]),
+        }
+    }
+
+    pub fn clear(&self) {
+        self.head.store(0, Ordering::SeqCst);
+    }
+
+    pub fn written(&self) -> u64 {
+        self.head.load(Ordering::Acquire)
+    }
+
+    pub fn write(&self, bytes: &[u8]) {
+        let start = self.head.fetch_add(bytes.len() as u64, Ordering::AcqRel);
+        let data = self.data.get() as *mut u8;
+
+        for (offset, byte) in bytes.iter().enumerate() {
+            let index = ((start + offset as u64) % RING_SIZE as u64) as usize;
+            unsafe { data.add(index).write_volatile(*byte) };
+        }
+    }
+
+    /// Calls `function` with the buffered bytes, oldest first. Writers may store into the ring
+    /// meanwhile, so the bytes are copied out in chunks, split between UTF-8 characters.
+    pub fn read<F>(&self, mut function: F)
+    where
+        F: FnMut(&[u8]),
+    {
+        let head = self.written();
+
+        // Once wrapped, the oldest line is partly overwritten; start after it.
+        let mut position = match head.checked_sub(RING_SIZE as u64) {
+            None | Some(0) => 0,
+            Some(start) => (start..head)
+                .find(|position| self.byte(*position) == b'\n')
+                .map_or(head, |position| position + 1),
+        };
+
+        let mut chunk = [0; CHUNK_SIZE];
+
+        while position < head {
+            let length = core::cmp::min(CHUNK_SIZE as u64, head - position) as usize;
+
+            for (offset, byte) in chunk[..length].iter_mut().enumerate() {
+                *byte = self.byte(position + offset as u64);
+            }
+
+            let length = match position + length as u64 == head {
+                true => length,
+                false => char_boundary(&chunk[..length]),
+            };
+
+            function(&chunk[..length]);
+            position += length as u64;
+        }
+    }
+
+    fn byte(&self, position: u64) -> u8 {
+        let index = (position % RING_SIZE as u64) as usize;
+        unsafe { (self.data.get() as *const u8).add(index).read_volatile() }
+    }
+
+    pub fn write_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let mut result = Ok(());
+
+        self.read(|bytes| {
+            for chunk in bytes.utf8_chunks() {
+                if result.is_ok() {
+                    result = out.write_str(chunk.valid());
+                }
+
+                if result.is_ok() && !chunk.invalid().is_empty() {
+                    result = out.write_char(char::REPLACEMENT_CHARACTER);
+                }
+            }
+        });
+
+        result
+    }
+}
+
+/// Length of the longest prefix that does not end inside a UTF-8 sequence.
+fn char_boundary(bytes: &[u8]) -> usize {
+    let tail = bytes.len().saturating_sub(3);
+
+    let lead = match bytes[tail..].iter().rposition(|byte| byte & 0xc0 != 0x80) {
+        Some(offset) => tail + offset,
+        None => return bytes.len(),
+    };
+
+    let sequence = match bytes[lead] {
+        0xf0..=0xff => 4,
+        0xe0..=0xef => 3,
+        0xc0..=0xdf => 2,
+        _ => 1,
+    };
+
+    match lead + sequence > bytes.len() {
+        true => lead,
+        false => bytes.len(),
+    }
+}
+
+impl fmt::Write for &Ring {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        self.write(string.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::format::FixedString;
+    use crate::libs::testing::kernel_test;
+    use core::fmt::Write;
+
+    fn contents(ring: &Ring) -> FixedString<64> {
+        let mut text = FixedString::new();
+        ring.write_to(&mut text).unwrap();
+        text
+    }
+
+    #[kernel_test]
+    fn test_write_read() {
+        static RING: Ring = Ring::new();
+        let ring = &RING;
+
+        writeln!(&mut &RING, "first").unwrap();
+        ring.write(b"second \xff\n");
+
+        assert_eq!(contents(ring).as_str(), "first\nsecond \u{fffd}\n");
+        assert_eq!(ring.written(), 15);
+    }
+
+    #[kernel_test]
+    fn test_wrapped_skips_partial_line() {
+        static RING: Ring = Ring::new();
+        let ring = &RING;
+
+        let line = [b'x'; 1023];
+        for _ in 0..RING_SIZE / 1024 {
+            ring.write(&line);
+            ring.write(b"\n");
+        }
+        ring.write(b"abc\nlast\n");
+
+        let mut lines = 0;
+        let mut first = [0; 4];
+        let mut total = 0;
+
+        ring.read(|bytes| {
+            if total == 0 && !bytes.is_empty() {
+                first.copy_from_slice(&bytes[..4]);
+            }
+            total += bytes.len();
+            lines += bytes.iter().filter(|byte| **byte == b'\n').count();
+        });
+
+        assert_eq!(&first, b"xxxx");
+        assert_eq!(lines, RING_SIZE / 1024 - 1 + 2);
+        assert_eq!(total, (RING_SIZE / 1024 - 1) * 1024 + 9);
+    }
+
+    #[kernel_test]
+    fn test_read_splits_between_characters() {
+        static RING: Ring = Ring::new();
+
+        RING.write(&[b'a'; CHUNK_SIZE - 1]);
+        RING.write("é€\n".as_bytes());
+
+        let mut total = 0;
+        RING.read(|bytes| {
+            assert!(core::str::from_utf8(bytes).is_ok());
+            total += bytes.len();
+        });
+
+        assert_eq!(total, CHUNK_SIZE - 1 + 6);
+    }
+
+    #[kernel_test]
+    fn test_char_boundary() {
+        assert_eq!(char_boundary(b"abc"), 3);
+        assert_eq!(char_boundary("aé".as_bytes()), 3);
+        assert_eq!(char_boundary(&"aé".as_bytes()[..2]), 1);
+        assert_eq!(char_boundary(&"a€".as_bytes()[..3]), 1);
+    }
+}
@@ -0,0 +1,95 @@
+#[derive(Clone, Copy)]
+pub struct FixedString<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> FixedString<N> {
+    pub const fn new() -> Self {
+        Self {
+            bytes: [0; N],
+            len: 0,
+        }
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    pub fn as_str(&self) -> &str {
+        match core::str::from_utf8(self.as_bytes()) {
+            Ok(string) => string,
+            Err(error) => {
+                core::str::from_utf8(&self.bytes[..error.valid_up_to()]).unwrap_or_default()
+            }
+        }
+    }
+}
+
+impl<const N: usize> Default for FixedString<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> core::fmt::Write for FixedString<N> {
+    fn write_str(&mut self, string: &str) -> core::fmt::Result {
+        let available = N - self.len;
+        let count = core::cmp::min(available, string.len());
+
+        self.bytes[self.len..self.len + count].copy_from_slice(&string.as_bytes()[..count]);
+        self.len += count;
+
+        match count == string.len() {
+            true => Ok(()),
+            false => Err(core::fmt::Error),
+        }
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for FixedString<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> core::fmt::Display for FixedString<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+    use core::fmt::Write;
+
+    #[kernel_test]
+    fn test_write() {
+        let mut string = FixedString::<16>::new();
+        write!(string, "{} + {}", 40, 2).unwrap();
+
+        assert_eq!(string.as_str(), "40 + 2");
+    }
+
+    #[kernel_test]
+    fn test_write_truncates() {
+        let mut string = FixedString::<4>::new();
+
+        assert!(write!(string, "abcdef").is_err());
+        assert_eq!(string.as_str(), "abcd");
+    }
+}
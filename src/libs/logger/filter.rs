@@ -0,0 +1,141 @@
+use super::LoggerError;
+use crate::libs::format::FixedString;
+use core::fmt::Write;
+use core::str::FromStr;
+use log::LevelFilter;
+
+const FILTER_SIZE: usize = 128;
+const DEFAULT_LEVEL: LevelFilter = LevelFilter::Info;
+
+/// Comma-separated directives, each either a bare level or `target=level`, as in
+/// `warn,ferros::driver=debug`. The most specific matching target wins.
+#[derive(Clone, Copy)]
+pub struct Filter {
+    spec: FixedString<FILTER_SIZE>,
+}
+
+impl Filter {
+    pub const fn new() -> Self {
+        Self {
+            spec: FixedString::new(),
+        }
+    }
+
+    pub fn parse(spec: &str) -> Result<Self, LoggerError> {
+        let mut filter = Self::new();
+        write!(filter.spec, "{}", spec).map_err(|_| LoggerError::FilterTooLong)?;
+
+        for directive in filter
+            .spec
+            .as_str()
+            .split(',')
+            .filter(|part| !part.is_empty())
+        {
+            parse_directive(directive).ok_or(LoggerError::InvalidFilter)?;
+        }
+
+        Ok(filter)
+    }
+
+    pub fn level(&self, target: &str) -> LevelFilter {
+        let mut level = self.default_level();
+        let mut matched = 0;
+
+        for (name, directive_level) in self.directives() {
+            if let Some(name) = name {
+                if name.len() > matched && matches_target(target, name) {
+                    level = directive_level;
+                    matched = name.len();
+                }
+            }
+        }
+
+        level
+    }
+
+    pub fn max_level(&self) -> LevelFilter {
+        self.directives()
+            .map(|(_, level)| level)
+            .chain(core::iter::once(self.default_level()))
+            .max()
+            .unwrap_or(DEFAULT_LEVEL)
+    }
+
+    fn default_level(&self) -> LevelFilter {
+        self.directives()
+            .filter(|(name, _)| name.is_none())
+            .map(|(_, level)| level)
+            .next_back()
+            .unwrap_or(DEFAULT_LEVEL)
+    }
+
+    fn directives(&self) -> impl DoubleEndedIterator<Item = (Option<&str>, LevelFilter)> + '_ {
+        self.spec.as_str().split(',').filter_map(parse_directive)
+    }
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_directive(directive: &str) -> Option<(Option<&str>, LevelFilter)> {
+    match directive.split_once('=') {
+        Some((name, level)) => Some((Some(name), LevelFilter::from_str(level).ok()?)),
+        None => Some((None, LevelFilter::from_str(directive).ok()?)),
+    }
+}
+
+fn matches_target(target: &str, name: &str) -> bool {
+    match target.strip_prefix(name) {
+        Some(rest) => rest.is_empty() || rest.starts_with("::"),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_default_level() {
+        let filter = Filter::new();
+
+        assert_eq!(filter.level("ferros::driver"), LevelFilter::Info);
+        assert_eq!(filter.max_level(), LevelFilter::Info);
+        assert_eq!(
+            Filter::parse("debug").unwrap().level("ferros"),
+            LevelFilter::Debug
+        );
+    }
+
+    #[kernel_test]
+    fn test_target_levels() {
+        let filter = Filter::parse("warn,ferros::driver=debug,ferros::driver::vga=off").unwrap();
+
+        assert_eq!(filter.level("ferros::nucleus"), LevelFilter::Warn);
+        assert_eq!(filter.level("ferros::driver"), LevelFilter::Debug);
+        assert_eq!(filter.level("ferros::driver::serial"), LevelFilter::Debug);
+        assert_eq!(filter.level("ferros::driver::vga::mode"), LevelFilter::Off);
+        assert_eq!(filter.level("ferros::drivers"), LevelFilter::Warn);
+        assert_eq!(filter.max_level(), LevelFilter::Debug);
+    }
+
+    #[kernel_test]
+    fn test_invalid_filter() {
+        assert_eq!(
+            Filter::parse("loud").err(),
+            Some(LoggerError::InvalidFilter)
+        );
+        assert_eq!(
+            Filter::parse("ferros=loud").err(),
+            Some(LoggerError::InvalidFilter)
+        );
+        assert_eq!(
+            Filter::parse(core::str::from_utf8(&[b'x'; FILTER_SIZE + 1]).unwrap()).err(),
+            Some(LoggerError::FilterTooLong)
+        );
+    }
+}
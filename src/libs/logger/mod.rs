@@ -0,0 +1,194 @@
+mod filter;
+mod sink;
+
+pub use filter::Filter;
+pub use sink::{DmesgSink, Entry, MemorySink, SerialSink, Sink, VgaSink, ENTRY_SIZE};
+
+use crate::libs::command_line;
+use crate::nucleus::clock;
+use core::fmt;
+use log::Level;
+use x86_64::instructions::interrupts;
+
+const MAX_SINKS: usize = 8;
+
+pub static SERIAL: SerialSink = SerialSink;
+pub static VGA: VgaSink = VgaSink;
+pub static MEMORY: MemorySink = MemorySink::new();
+pub static DMESG: DmesgSink = DmesgSink;
+
+static LOGGER: Logger = Logger;
+static FILTER: spin::Mutex<Filter> = spin::Mutex::new(Filter::new());
+static SINKS: spin::Mutex<[Option<&'static dyn Sink>; MAX_SINKS]> =
+    spin::Mutex::new([None; MAX_SINKS]);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoggerError {
+    FilterTooLong,
+    InvalidFilter,
+    TooManySinks,
+    AlreadyInitialized,
+}
+
+/// A log record as handed to sinks: `[seconds.micros] LEVEL target: message`.
+pub struct Line<'a> {
+    pub timestamp: u64,
+    pub level: Level,
+    pub target: &'a str,
+    pub message: fmt::Arguments<'a>,
+}
+
+impl Line<'_> {
+    pub fn timestamp(&self) -> Timestamp {
+        Timestamp(self.timestamp)
+    }
+}
+
+impl fmt::Display for Line<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{} {:<5} {}: {}",
+            self.timestamp(),
+            self.level,
+            self.target,
+            self.message
+        )
+    }
+}
+
+pub struct Timestamp(u64);
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "[{:5}.{:06}]",
+            self.0 / 1_000_000,
+            self.0 % 1_000_000
+        )
+    }
+}
+
+struct Logger;
+
+impl log::Log for Logger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        let filter = interrupts::without_interrupts(|| *FILTER.lock());
+        metadata.level() <= filter.level(metadata.target())
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = Line {
+            timestamp: clock::cycles_to_microseconds(clock::timestamp()),
+            level: record.level(),
+            target: record.target(),
+            message: *record.args(),
+        };
+
+        let sinks = interrupts::without_interrupts(|| *SINKS.lock());
+
+        for sink in sinks.iter().flatten() {
+            sink.write(&line);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Installs the logger with the serial, VGA, memory and dmesg sinks. The level filter comes from the
+/// `log=` kernel command-line argument and defaults to `info`.
+pub fn init() -> Result<(), LoggerError> {
+    let mut buffer = [0; command_line::COMMAND_LINE_SIZE];
+    let spec = command_line::value(command_line::read(&mut buffer), "log");
+    let filter_result = set_filter(spec.unwrap_or(""));
+
+    log::set_logger(&LOGGER).map_err(|_| LoggerError::AlreadyInitialized)?;
+
+    add_sink(&SERIAL)?;
+    add_sink(&VGA)?;
+    add_sink(&MEMORY)?;
+    add_sink(&DMESG)?;
+
+    if let Err(error) = filter_result {
+        log::warn!("ignoring log filter {:?}: {:?}", spec.unwrap_or(""), error);
+    }
+
+    Ok(())
+}
+
+pub fn set_filter(spec: &str) -> Result<(), LoggerError> {
+    let filter = Filter::parse(spec)?;
+
+    interrupts::without_interrupts(|| *FILTER.lock() = filter);
+    log::set_max_level(filter.max_level());
+    Ok(())
+}
+
+pub fn add_sink(sink: &'static dyn Sink) -> Result<(), LoggerError> {
+    interrupts::without_interrupts(|| {
+        let mut sinks = SINKS.lock();
+        let slot = sinks
+            .iter_mut()
+            .find(|slot| slot.is_none())
+            .ok_or(LoggerError::TooManySinks)?;
+
+        *slot = Some(sink);
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::format::FixedString;
+    use crate::libs::testing::kernel_test;
+    use core::fmt::Write;
+
+    #[kernel_test]
+    fn test_line_format() {
+        let mut text = FixedString::<128>::new();
+        let ports = 2;
+        let line = Line {
+            timestamp: 12_345_678,
+            level: Level::Warn,
+            target: "ferros::driver::serial",
+            message: format_args!("detected {} ports", ports),
+        };
+
+        write!(text, "{}", line).unwrap();
+
+        assert_eq!(
+            text.as_str(),
+            "[   12.345678] WARN  ferros::driver::serial: detected 2 ports"
+        );
+        assert_eq!(Entry::new(&line).text.as_str(), text.as_str());
+    }
+
+    #[cfg(target_os = "none")]
+    #[test_case]
+    fn test_memory_sink() {
+        let line = Line {
+            timestamp: 0,
+            level: Level::Info,
+            target: "ferros::libs::logger",
+            message: format_args!("stored"),
+        };
+
+        MEMORY.write(&line);
+
+        let mut last = None;
+        MEMORY.for_each(|entry| last = Some(*entry));
+
+        assert_eq!(last.map(|entry| entry.level), Some(Level::Info));
+        assert!(last
+            .unwrap()
+            .text
+            .as_str()
+            .ends_with("ferros::libs::logger: stored"));
+    }
+}
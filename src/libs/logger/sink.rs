@@ -0,0 +1,111 @@
+use super::Line;
+use crate::driver::vga::{self, Color, ColorName, Style};
+use crate::libs::buffer::RingBuffer;
+use crate::libs::format::FixedString;
+use core::fmt::Write;
+use log::Level;
+use x86_64::instructions::interrupts;
+
+const MEMORY_ENTRIES: usize = 64;
+pub const ENTRY_SIZE: usize = 160;
+
+pub trait Sink: Sync {
+    fn write(&self, line: &Line);
+}
+
+pub struct SerialSink;
+
+impl Sink for SerialSink {
+    fn write(&self, line: &Line) {
+        crate::serial_println!("{}", line);
+    }
+}
+
+pub struct DmesgSink;
+
+impl Sink for DmesgSink {
+    fn write(&self, line: &Line) {
+        crate::libs::dmesg::print(format_args!("{}\n", line));
+    }
+}
+
+pub struct VgaSink;
+
+impl Sink for VgaSink {
+    fn write(&self, line: &Line) {
+        // The dmesg sink logs the line, so it only goes to the screen here.
+        vga::print_screen(format_args!("{} ", line.timestamp()));
+        vga::with_style(level_style(line.level), || {
+            vga::print_screen(format_args!("{:<5}", line.level))
+        });
+        vga::print_screen(format_args!(" {}: {}\n", line.target, line.message));
+    }
+}
+
+fn level_style(level: Level) -> Style {
+    let color = match level {
+        Level::Error => Color::Bright(ColorName::Red),
+        Level::Warn => Color::Bright(ColorName::Yellow),
+        Level::Info => Color::Bright(ColorName::Green),
+        Level::Debug => Color::Bright(ColorName::Cyan),
+        Level::Trace => Color::Dim(ColorName::White),
+    };
+
+    Style::new().foreground(color)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Entry {
+    pub level: Level,
+    pub text: FixedString<ENTRY_SIZE>,
+}
+
+impl Entry {
+    const EMPTY: Entry = Entry {
+        level: Level::Trace,
+        text: FixedString::new(),
+    };
+
+    pub fn new(line: &Line) -> Self {
+        let mut entry = Self {
+            level: line.level,
+            text: FixedString::new(),
+        };
+
+        write!(entry.text, "{}", line).ok();
+        entry
+    }
+}
+
+/// Keeps the most recent log lines, truncated to `ENTRY_SIZE` bytes each.
+pub struct MemorySink {
+    entries: spin::Mutex<RingBuffer<Entry, MEMORY_ENTRIES>>,
+}
+
+impl MemorySink {
+    pub const fn new() -> Self {
+        Self {
+            entries: spin::Mutex::new(RingBuffer::new_with_default(Entry::EMPTY)),
+        }
+    }
+
+    pub fn for_each<F>(&self, function: F)
+    where
+        F: FnMut(&Entry),
+    {
+        interrupts::without_interrupts(|| self.entries.lock().iter().for_each(function));
+    }
+}
+
+impl Sink for MemorySink {
+    fn write(&self, line: &Line) {
+        let entry = Entry::new(line);
+        interrupts::without_interrupts(|| self.entries.lock().push_overwrite(entry));
+    }
+}
+
+impl Default for MemorySink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,2 +1,7 @@
+pub mod backtrace;
 pub mod buffer;
+pub mod command_line;
+pub mod dmesg;
+pub mod format;
+pub mod logger;
 pub mod testing;
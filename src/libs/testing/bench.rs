@@ -0,0 +1,303 @@
+use super::{runner, traits};
+use crate::nucleus::clock;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+const SAMPLES: usize = 64;
+const WARMUP_SAMPLES: usize = 4;
+const MAX_BATCH: u64 = 1 << 20;
+const SAMPLE_MICROSECONDS: u64 = 100;
+const DEFAULT_TIMEOUT: u64 = 30_000;
+
+const BASELINE_FILE: &str = "opt/ferros/baseline";
+const BASELINE_SIZE: usize = 4096;
+
+static MEASURE: AtomicBool = AtomicBool::new(false);
+static STATISTICS: spin::Mutex<Option<Statistics>> = spin::Mutex::new(None);
+static BASELINE: spin::Mutex<Baseline> = spin::Mutex::new(Baseline::empty());
+
+/// Per-iteration timings of a benchmark, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Statistics {
+    pub mean: u64,
+    pub median: u64,
+    pub deviation: u64,
+    pub iterations: u64,
+    pub baseline: Option<u64>,
+}
+
+impl Statistics {
+    pub fn from_samples(samples: &mut [u64], iterations: u64) -> Self {
+        samples.sort_unstable();
+
+        let count = core::cmp::max(samples.len(), 1) as u64;
+        let mean = samples.iter().sum::<u64>() / count;
+        let variance = samples
+            .iter()
+            .map(|sample| sample.abs_diff(mean).pow(2))
+            .sum::<u64>()
+            / count;
+
+        let median = match samples.len() {
+            0 => 0,
+            length if length % 2 == 0 => (samples[length / 2 - 1] + samples[length / 2]) / 2,
+            length => samples[length / 2],
+        };
+
+        Self {
+            mean,
+            median,
+            deviation: variance.isqrt(),
+            iterations,
+            baseline: None,
+        }
+    }
+
+    /// Change of the mean against the baseline, in tenths of a percent.
+    pub fn change(&self) -> Option<i64> {
+        match self.baseline? {
+            0 => None,
+            baseline => Some((self.mean as i64 - baseline as i64) * 1000 / baseline as i64),
+        }
+    }
+}
+
+pub struct Bencher {
+    measure: bool,
+    statistics: Option<Statistics>,
+}
+
+impl Bencher {
+    pub fn iter<T, F>(&mut self, mut routine: F)
+    where
+        F: FnMut() -> T,
+    {
+        if !self.measure {
+            core::hint::black_box(routine());
+            return;
+        }
+
+        let cycles_per_microsecond = clock::cycles_per_microsecond();
+        let batch = calibrate(&mut routine, cycles_per_microsecond * SAMPLE_MICROSECONDS);
+
+        for _ in 0..WARMUP_SAMPLES {
+            run_batch(&mut routine, batch);
+        }
+
+        let mut samples = [0; SAMPLES];
+
+        for sample in samples.iter_mut() {
+            let cycles = run_batch(&mut routine, batch);
+            *sample = cycles * 1_000 / (cycles_per_microsecond * batch);
+        }
+
+        self.statistics = Some(Statistics::from_samples(
+            &mut samples,
+            batch * SAMPLES as u64,
+        ));
+    }
+}
+
+fn calibrate<T, F: FnMut() -> T>(routine: &mut F, target_cycles: u64) -> u64 {
+    let mut batch = 1;
+
+    while batch < MAX_BATCH && run_batch(routine, batch) < target_cycles {
+        batch *= 2;
+    }
+
+    batch
+}
+
+fn run_batch<T, F: FnMut() -> T>(routine: &mut F, batch: u64) -> u64 {
+    let start = clock::timestamp();
+
+    for _ in 0..batch {
+        core::hint::black_box(routine());
+    }
+
+    clock::timestamp() - start
+}
+
+pub struct KernelBench {
+    name: &'static str,
+    function: fn(&mut Bencher),
+    ignored: bool,
+    timeout: Option<u64>,
+    tags: &'static [&'static str],
+}
+
+impl KernelBench {
+    pub const fn new(name: &'static str, function: fn(&mut Bencher)) -> Self {
+        Self {
+            name,
+            function,
+            ignored: false,
+            timeout: None,
+            tags: &[],
+        }
+    }
+
+    pub const fn ignore(mut self) -> Self {
+        self.ignored = true;
+        self
+    }
+
+    pub const fn timeout(mut self, milliseconds: u64) -> Self {
+        self.timeout = Some(milliseconds);
+        self
+    }
+
+    pub const fn tags(mut self, tags: &'static [&'static str]) -> Self {
+        self.tags = tags;
+        self
+    }
+}
+
+impl traits::Testable for KernelBench {
+    fn run(&self) -> runner::TestReport {
+        *STATISTICS.lock() = None;
+
+        let timeout = match MEASURE.load(Ordering::Relaxed) {
+            true => Some(self.timeout.unwrap_or(DEFAULT_TIMEOUT)),
+            false => self.timeout,
+        };
+
+        let mut report = runner::run_test(traits::Expectation::Success, timeout, || {
+            let statistics = run_bench(self.function);
+            *STATISTICS.lock() = statistics;
+        });
+
+        report.benchmark = STATISTICS.lock().take().map(|statistics| Statistics {
+            baseline: BASELINE.lock().mean(self.name),
+            ..statistics
+        });
+        report
+    }
+
+    fn should_panic(&self) -> bool {
+        false
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn ignored(&self) -> bool {
+        self.ignored
+    }
+
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        self.tags
+    }
+
+    fn benchmark(&self) -> bool {
+        true
+    }
+}
+
+/// Runs a benchmark function, measuring it only in benchmark mode. Otherwise its routine is run
+/// once, as a test.
+pub fn run_bench(function: fn(&mut Bencher)) -> Option<Statistics> {
+    let mut bencher = Bencher {
+        measure: MEASURE.load(Ordering::Relaxed),
+        statistics: None,
+    };
+
+    function(&mut bencher);
+    bencher.statistics
+}
+
+pub(super) fn set_measure(measure: bool) {
+    MEASURE.store(measure, Ordering::Relaxed);
+
+    if measure {
+        BASELINE.lock().load();
+    }
+}
+
+struct Baseline {
+    data: [u8; BASELINE_SIZE],
+    length: usize,
+}
+
+impl Baseline {
+    const fn empty() -> Self {
+        Self {
+            data: [0; BASELINE_SIZE],
+            length: 0,
+        }
+    }
+
+    fn load(&mut self) {
+        self.length = crate::driver::fw_cfg::read_file(BASELINE_FILE, &mut self.data).unwrap_or(0);
+    }
+
+    fn mean(&self, name: &str) -> Option<u64> {
+        let text = core::str::from_utf8(&self.data[..self.length]).ok()?;
+        parse_baseline(text, name)
+    }
+}
+
+fn parse_baseline(text: &str, name: &str) -> Option<u64> {
+    text.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+
+        match fields.next() == Some(name) {
+            true => fields.next()?.parse().ok(),
+            false => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_statistics() {
+        let mut samples = [12, 10, 14, 10, 30, 11];
+        let statistics = Statistics::from_samples(&mut samples, 600);
+
+        assert_eq!(statistics.mean, 14);
+        assert_eq!(statistics.median, 11);
+        assert_eq!(statistics.deviation, 7);
+        assert_eq!(statistics.iterations, 600);
+        assert_eq!(Statistics::from_samples(&mut [], 0).mean, 0);
+    }
+
+    #[kernel_test]
+    fn test_change() {
+        let mut statistics = Statistics::from_samples(&mut [105], 1);
+
+        assert_eq!(statistics.change(), None);
+
+        statistics.baseline = Some(100);
+        assert_eq!(statistics.change(), Some(50));
+
+        statistics.baseline = Some(210);
+        assert_eq!(statistics.change(), Some(-500));
+    }
+
+    #[kernel_test]
+    fn test_parse_baseline() {
+        let text = "ferros::a::bench_one 120\nferros::a::bench_two 7\nbroken\n";
+
+        assert_eq!(parse_baseline(text, "ferros::a::bench_two"), Some(7));
+        assert_eq!(parse_baseline(text, "ferros::a::bench_one"), Some(120));
+        assert_eq!(parse_baseline(text, "ferros::a::bench"), None);
+        assert_eq!(parse_baseline(text, "broken"), None);
+    }
+
+    #[kernel_test]
+    fn test_run_without_measuring() {
+        fn bench(bencher: &mut Bencher) {
+            bencher.iter(|| 1 + 1);
+        }
+
+        assert_eq!(run_bench(bench), None);
+    }
+}
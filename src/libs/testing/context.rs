@@ -0,0 +1,112 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+
+static RESUME_STACK_POINTER: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Returned,
+    Panicked,
+}
+
+pub fn run_guarded_on_stack<F>(stack: &mut [u8], function: F) -> Outcome
+where
+    F: FnOnce(),
+{
+    let stack_top = stack.as_mut_ptr_range().end as u64;
+    unsafe { run(function, stack_top) }
+}
+
+unsafe fn run<F>(function: F, stack_top: u64) -> Outcome
+where
+    F: FnOnce(),
+{
+    extern "C" fn trampoline<F: FnOnce()>(argument: *mut ()) {
+        let function = unsafe { &mut *(argument as *mut Option<F>) };
+
+        if let Some(function) = function.take() {
+            function();
+        }
+    }
+
+    let mut function = Some(function);
+    let previous = RESUME_STACK_POINTER.load(Ordering::SeqCst);
+
+    let result = call_guarded(
+        trampoline::<F>,
+        &mut function as *mut Option<F> as *mut (),
+        RESUME_STACK_POINTER.as_ptr(),
+        stack_top,
+    );
+
+    RESUME_STACK_POINTER.store(previous, Ordering::SeqCst);
+
+    match result {
+        0 => Outcome::Returned,
+        _ => Outcome::Panicked,
+    }
+}
+
+pub fn is_guarded() -> bool {
+    RESUME_STACK_POINTER.load(Ordering::SeqCst) != 0
+}
+
+pub fn resume() -> ! {
+    let stack_pointer = RESUME_STACK_POINTER.load(Ordering::SeqCst);
+    assert_ne!(stack_pointer, 0, "No guarded context to resume");
+
+    unsafe { resume_at(stack_pointer) }
+}
+
+#[unsafe(naked)]
+unsafe extern "C" fn call_guarded(
+    function: extern "C" fn(*mut ()),
+    argument: *mut (),
+    stack_pointer: *mut u64,
+    stack_top: u64,
+) -> u64 {
+    core::arch::naked_asm!(
+        "push rbp",
+        "push rbx",
+        "push r12",
+        "push r13",
+        "push r14",
+        "push r15",
+        "sub rsp, 8",
+        "mov [rdx], rsp",
+        "mov rbx, rdx",
+        "test rcx, rcx",
+        "jz 2f",
+        "and rcx, -16",
+        "mov rsp, rcx",
+        "2:",
+        "mov rax, rdi",
+        "mov rdi, rsi",
+        "call rax",
+        "mov rsp, [rbx]",
+        "xor eax, eax",
+        "add rsp, 8",
+        "pop r15",
+        "pop r14",
+        "pop r13",
+        "pop r12",
+        "pop rbx",
+        "pop rbp",
+        "ret",
+    )
+}
+
+#[unsafe(naked)]
+unsafe extern "C" fn resume_at(stack_pointer: u64) -> ! {
+    core::arch::naked_asm!(
+        "mov rsp, rdi",
+        "mov eax, 1",
+        "add rsp, 8",
+        "pop r15",
+        "pop r14",
+        "pop r13",
+        "pop r12",
+        "pop rbx",
+        "pop rbp",
+        "ret",
+    )
+}
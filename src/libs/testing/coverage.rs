@@ -0,0 +1,38 @@
+#[cfg(feature = "coverage")]
+use crate::serial_println;
+
+#[cfg(feature = "coverage")]
+const DEBUGCON_PORT: u16 = 0xe9;
+
+#[cfg(feature = "coverage")]
+struct Debugcon(x86_64::instructions::port::Port<u8>);
+
+#[cfg(feature = "coverage")]
+impl minicov::CoverageWriter for Debugcon {
+    fn write(&mut self, data: &[u8]) -> Result<(), minicov::CoverageWriteError> {
+        for byte in data {
+            unsafe { self.0.write(*byte) };
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the LLVM coverage counters of an instrumented build to QEMU's isa-debugcon, which stores
+/// them byte for byte in a `.profraw` file on the host (see `scripts/coverage.sh`).
+#[cfg(feature = "coverage")]
+pub(super) fn dump() {
+    let mut debugcon = Debugcon(x86_64::instructions::port::Port::new(DEBUGCON_PORT));
+
+    match unsafe { minicov::capture_coverage(&mut debugcon) } {
+        Ok(()) => {
+            serial_println!("coverage: profile written to debugcon");
+        }
+        Err(_) => {
+            serial_println!("coverage: failed to write profile");
+        }
+    }
+}
+
+#[cfg(not(feature = "coverage"))]
+pub(super) fn dump() {}
@@ -0,0 +1,194 @@
+use super::traits::Testable;
+use crate::libs::command_line;
+
+const TAG_PREFIX: &str = "tag=";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ignored {
+    Skip,
+    Include,
+    Only,
+}
+
+pub(super) struct Filter<'a> {
+    command_line: &'a str,
+    exact: bool,
+    exit_on_timeout: bool,
+    update_snapshots: bool,
+    benchmarks: bool,
+    ignored: Ignored,
+}
+
+impl<'a> Filter<'a> {
+    pub(super) fn parse(command_line: &'a str) -> Self {
+        let mut filter = Self {
+            command_line,
+            exact: false,
+            exit_on_timeout: false,
+            update_snapshots: false,
+            benchmarks: false,
+            ignored: Ignored::Skip,
+        };
+
+        for argument in command_line.split_whitespace() {
+            match argument {
+                "--exact" => filter.exact = true,
+                "--exit-on-timeout" => filter.exit_on_timeout = true,
+                "--update-snapshots" => filter.update_snapshots = true,
+                "--bench" => filter.benchmarks = true,
+                "--include-ignored" => filter.ignored = Ignored::Include,
+                "--ignored" => filter.ignored = Ignored::Only,
+                _ => {}
+            }
+        }
+
+        filter
+    }
+
+    pub(super) fn selects(&self, test: &dyn Testable) -> bool {
+        if self.ignored == Ignored::Only && !test.ignored() {
+            return false;
+        }
+
+        if self.benchmarks && !test.benchmark() {
+            return false;
+        }
+
+        self.matches_name(test.name()) && self.matches_tags(test.tags())
+    }
+
+    pub(super) fn runs_ignored(&self) -> bool {
+        self.ignored != Ignored::Skip
+    }
+
+    pub(super) fn exits_on_timeout(&self) -> bool {
+        self.exit_on_timeout
+    }
+
+    pub(super) fn updates_snapshots(&self) -> bool {
+        self.update_snapshots
+    }
+
+    pub(super) fn benchmarks(&self) -> bool {
+        self.benchmarks
+    }
+
+    pub(super) fn seed(&self) -> Option<u64> {
+        let seed = command_line::value(self.command_line, "seed")?;
+
+        match seed.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+            None => seed.parse().ok(),
+        }
+    }
+
+    fn matches_name(&self, name: &str) -> bool {
+        let mut patterns = self
+            .command_line
+            .split_whitespace()
+            .filter(|argument| !argument.starts_with("--") && !argument.contains('='))
+            .peekable();
+
+        if patterns.peek().is_none() {
+            return true;
+        }
+
+        patterns.any(|pattern| match self.exact {
+            true => name == pattern,
+            false => name.contains(pattern),
+        })
+    }
+
+    fn matches_tags(&self, tags: &[&str]) -> bool {
+        let mut selected = self
+            .command_line
+            .split_whitespace()
+            .filter_map(|argument| argument.strip_prefix(TAG_PREFIX))
+            .peekable();
+
+        if selected.peek().is_none() {
+            return true;
+        }
+
+        selected.any(|tag| tags.contains(&tag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+    use crate::libs::testing::KernelTest;
+
+    const PLAIN: KernelTest = KernelTest::new("ferros::libs::buffer::tests::test_fill", || {});
+    const TAGGED: KernelTest =
+        KernelTest::new("ferros::driver::vga::tests::test_mode", || {}).tags(&["vga", "slow"]);
+    const IGNORED: KernelTest =
+        KernelTest::new("ferros::driver::vga::tests::test_font", || {}).ignore();
+
+    #[kernel_test]
+    fn test_empty_filter() {
+        let filter = Filter::parse("");
+
+        assert!(filter.selects(&PLAIN));
+        assert!(filter.selects(&IGNORED));
+        assert!(!filter.runs_ignored());
+        assert!(!filter.exits_on_timeout());
+        assert!(Filter::parse("--exit-on-timeout").exits_on_timeout());
+        assert!(Filter::parse("--update-snapshots").updates_snapshots());
+    }
+
+    #[kernel_test]
+    fn test_name_filter() {
+        let filter = Filter::parse("buffer font");
+
+        assert!(filter.selects(&PLAIN));
+        assert!(!filter.selects(&TAGGED));
+        assert!(filter.selects(&IGNORED));
+
+        let filter = Filter::parse("--exact test_fill");
+        assert!(!filter.selects(&PLAIN));
+    }
+
+    #[kernel_test]
+    fn test_tag_filter() {
+        let filter = Filter::parse("tag=slow");
+
+        assert!(!filter.selects(&PLAIN));
+        assert!(filter.selects(&TAGGED));
+    }
+
+    #[kernel_test]
+    fn test_ignored_filter() {
+        let filter = Filter::parse("--ignored");
+
+        assert!(!filter.selects(&PLAIN));
+        assert!(filter.selects(&IGNORED));
+        assert!(filter.runs_ignored());
+    }
+
+    #[kernel_test]
+    fn test_bench_filter() {
+        fn bench(_bencher: &mut crate::libs::testing::Bencher) {}
+        let benchmark = crate::libs::testing::KernelBench::new(
+            "ferros::libs::buffer::tests::bench_shift",
+            bench,
+        );
+
+        let filter = Filter::parse("--bench");
+        assert!(filter.benchmarks());
+        assert!(!filter.selects(&PLAIN));
+        assert!(filter.selects(&benchmark));
+        assert!(Filter::parse("").selects(&benchmark));
+    }
+
+    #[kernel_test]
+    fn test_seed() {
+        assert_eq!(Filter::parse("").seed(), None);
+        assert_eq!(Filter::parse("seed=42").seed(), Some(42));
+        assert_eq!(Filter::parse("buffer seed=0x2a").seed(), Some(42));
+        assert_eq!(Filter::parse("seed=nope").seed(), None);
+        assert!(Filter::parse("seed=42").selects(&PLAIN));
+        assert!(Filter::parse("log=debug").selects(&PLAIN));
+    }
+}
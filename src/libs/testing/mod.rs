@@ -1,20 +1,114 @@
+mod bench;
+mod context;
+mod coverage;
+mod filter;
+mod output;
+pub mod property;
 mod qemu;
-mod serial;
+mod runner;
+mod snapshot;
 mod traits;
+mod watchdog;
+
+use crate::libs::command_line;
+
+pub use bench::{run_bench, Bencher, KernelBench, Statistics};
+pub use ferros_macros::{kernel_bench, kernel_test};
+pub use output::{Human, JUnit, Output, Tap};
+pub use runner::{Failure, Summary, TestReport, TestResult};
+pub use snapshot::check_snapshot;
+pub use traits::{KernelTest, ShouldPanic};
 
 pub fn panic(info: &core::panic::PanicInfo) -> ! {
-    serial::serial_println!("[failed]\n");
-    serial::serial_println!("Error: {}\n", info);
+    let backtrace = crate::libs::backtrace::Backtrace::capture();
+    runner::handle_panic(info, &backtrace);
+
+    output::Selected::bail_out(info, &backtrace);
+    coverage::dump();
     qemu::fail();
     loop {}
 }
 
 pub fn test_runner(tests: &[&dyn traits::Testable]) {
-    serial::serial_println!("Running {} tests", tests.len());
+    let mut buffer = [0; command_line::COMMAND_LINE_SIZE];
+    let filter = filter::Filter::parse(command_line::read(&mut buffer));
+
+    let selected = tests.iter().filter(|test| filter.selects(**test)).count();
+
+    crate::nucleus::clock::cycles_per_microsecond();
+    snapshot::set_update_mode(filter.updates_snapshots());
+    property::set_seed(filter.seed());
+    bench::set_measure(filter.benchmarks());
+    watchdog::init();
+    output::Selected::start(selected);
+
+    let mut summary = runner::Summary::new();
+    summary.filter_out(tests.len() - selected);
+
+    let tests = tests.iter().filter(|test| filter.selects(**test));
 
-    for test in tests {
-        test.run();
+    for (index, test) in tests.enumerate() {
+        output::Selected::test_started(index, test.name());
+
+        let report = match test.ignored() && !filter.runs_ignored() {
+            true => runner::TestReport::ignored(),
+            false => test.run(),
+        };
+        output::Selected::test_finished(index, test.name(), &report);
+
+        summary.record(test.name(), report.result);
+
+        if report.result == runner::TestResult::TimedOut && filter.exits_on_timeout() {
+            output::Selected::finish(&summary);
+            coverage::dump();
+            qemu::timeout();
+        }
     }
 
-    qemu::success();
+    output::Selected::finish(&summary);
+    coverage::dump();
+
+    match summary.is_success() {
+        true => qemu::success(),
+        false => qemu::fail(),
+    }
+}
+
+#[cfg(all(test, target_os = "none"))]
+mod tests {
+    use super::{kernel_test, ShouldPanic};
+
+    #[test_case]
+    fn test_panic__should_panic() {
+        panic!("Expected panic");
+    }
+
+    #[test_case]
+    const TEST_PANIC_WITH_MESSAGE: ShouldPanic =
+        ShouldPanic::new("test_panic_with_message", || {
+            let values: [u8; 2] = [1, 2];
+            let index = values.len() + core::hint::black_box(1);
+            let _ = values[index];
+        })
+        .expected("index out of bounds");
+
+    #[test_case]
+    fn test_ignored__ignore() {
+        panic!("Ignored tests are not run");
+    }
+
+    #[kernel_test(should_panic(expected = "attribute"), tags(testing))]
+    fn test_kernel_test_should_panic() {
+        panic!("Expected attribute panic");
+    }
+
+    #[kernel_test(ignore)]
+    fn test_kernel_test_ignored() {
+        panic!("Ignored kernel tests are not run");
+    }
+
+    #[kernel_test(timeout = 1000)]
+    fn test_kernel_test_timeout() {
+        assert_eq!(1 + 1, 2);
+    }
 }
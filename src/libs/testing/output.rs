@@ -0,0 +1,398 @@
+use super::bench::Statistics;
+use super::runner::{Failure, Summary, TestReport, TestResult};
+use crate::libs::backtrace::Backtrace;
+use crate::libs::format::FixedString;
+use crate::{serial_print, serial_println};
+use core::fmt;
+
+#[cfg(all(feature = "test-output-tap", feature = "test-output-junit"))]
+compile_error!("features `test-output-tap` and `test-output-junit` are mutually exclusive");
+
+#[cfg(feature = "test-output-tap")]
+pub(super) type Selected = Tap;
+
+#[cfg(feature = "test-output-junit")]
+pub(super) type Selected = JUnit;
+
+#[cfg(not(any(feature = "test-output-tap", feature = "test-output-junit")))]
+pub(super) type Selected = Human;
+
+pub trait Output {
+    fn start(count: usize);
+    fn test_started(index: usize, name: &str);
+    fn test_finished(index: usize, name: &str, report: &TestReport);
+    fn finish(summary: &Summary);
+    fn bail_out(info: &core::panic::PanicInfo, backtrace: &Backtrace);
+}
+
+pub struct Human;
+
+impl Output for Human {
+    fn start(count: usize) {
+        serial_println!("Running {} tests", count);
+    }
+
+    fn test_started(_index: usize, name: &str) {
+        serial_print!("{}.........\t", name);
+    }
+
+    fn test_finished(_index: usize, _name: &str, report: &TestReport) {
+        match (report.result, report.failure.as_ref()) {
+            (TestResult::Passed, _) => match report.benchmark.as_ref() {
+                Some(statistics) => {
+                    serial_println!("[ok] bench: {}", Bench(statistics));
+                }
+                None => {
+                    serial_println!("[ok]");
+                }
+            },
+            (TestResult::Ignored, _) => {
+                serial_println!("[ignored]");
+            }
+            (TestResult::Failed, Some(failure)) if !failure.location.is_empty() => {
+                serial_println!("[failed]\n");
+                serial_println!(
+                    "Error: panicked at {}:\n{}\n",
+                    failure.location,
+                    failure.message
+                );
+                print_backtrace(failure);
+            }
+            (TestResult::TimedOut, failure) => {
+                serial_println!("[timeout]\n");
+                serial_println!(
+                    "Error: {}\n",
+                    failure.map_or("timed out", |failure| failure.message.as_str())
+                );
+            }
+            (TestResult::Failed, failure) => {
+                serial_println!("[failed]\n");
+                serial_println!(
+                    "Error: {}\n",
+                    failure.map_or("unknown failure", |failure| failure.message.as_str())
+                );
+
+                if let Some(failure) = failure {
+                    print_backtrace(failure);
+                }
+            }
+        }
+    }
+
+    fn finish(summary: &Summary) {
+        summary.report();
+    }
+
+    fn bail_out(info: &core::panic::PanicInfo, backtrace: &Backtrace) {
+        serial_println!("[failed]\n");
+        serial_println!("Error: {}\n", info);
+        serial_println!("{}", backtrace);
+    }
+}
+
+pub struct Tap;
+
+impl Output for Tap {
+    fn start(count: usize) {
+        serial_println!("TAP version 13");
+        serial_println!("1..{}", count);
+    }
+
+    fn test_started(_index: usize, _name: &str) {}
+
+    fn test_finished(index: usize, name: &str, report: &TestReport) {
+        let number = index + 1;
+
+        match report.result {
+            TestResult::Passed => {
+                serial_println!("ok {} - {}", number, name);
+            }
+            TestResult::Ignored => {
+                serial_println!("ok {} - {} # SKIP", number, name);
+            }
+            TestResult::Failed | TestResult::TimedOut => {
+                serial_println!("not ok {} - {}", number, name);
+            }
+        }
+
+        if report.result == TestResult::Ignored {
+            return;
+        }
+
+        serial_println!("  ---");
+        serial_println!("  duration_ms: {}", Milliseconds(report.duration));
+
+        if let Some(statistics) = report.benchmark.as_ref() {
+            serial_println!("  bench_mean_ns: {}", statistics.mean);
+            serial_println!("  bench_median_ns: {}", statistics.median);
+            serial_println!("  bench_deviation_ns: {}", statistics.deviation);
+            serial_println!("  bench_iterations: {}", statistics.iterations);
+
+            if let Some(baseline) = statistics.baseline {
+                serial_println!("  bench_baseline_ns: {}", baseline);
+            }
+        }
+
+        if let Some(failure) = report.failure.as_ref() {
+            serial_println!("  message: {:?}", failure.message.as_str());
+
+            if !failure.location.is_empty() {
+                serial_println!("  location: {:?}", failure.location.as_str());
+            }
+
+            if let Some(backtrace) = failure.backtrace.as_ref() {
+                serial_println!("  backtrace:");
+
+                for frame in backtrace.frames() {
+                    serial_println!("    - \"{}\"", frame);
+                }
+            }
+        }
+
+        serial_println!("  ...");
+    }
+
+    fn finish(summary: &Summary) {
+        serial_println!("# passed {}", summary.passed());
+        serial_println!("# failed {}", summary.failed());
+        serial_println!("# ignored {}", summary.ignored());
+    }
+
+    fn bail_out(info: &core::panic::PanicInfo, backtrace: &Backtrace) {
+        match info.location() {
+            Some(location) => {
+                serial_println!("Bail out! {} at {}", info.message(), location);
+            }
+            None => {
+                serial_println!("Bail out! {}", info.message());
+            }
+        }
+
+        for frame in backtrace.frames() {
+            serial_println!("# {}", frame);
+        }
+    }
+}
+
+pub struct JUnit;
+
+impl Output for JUnit {
+    fn start(count: usize) {
+        serial_println!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+        serial_println!("<testsuites>");
+        serial_println!(
+            "  <testsuite name=\"{}\" tests=\"{}\">",
+            env!("CARGO_PKG_NAME"),
+            count
+        );
+    }
+
+    fn test_started(_index: usize, _name: &str) {}
+
+    fn test_finished(_index: usize, name: &str, report: &TestReport) {
+        let (class_name, test_name) = split_name(name);
+
+        serial_print!(
+            "    <testcase classname=\"{}\" name=\"{}\" time=\"{}\"",
+            Xml(class_name),
+            Xml(test_name),
+            Seconds(report.duration)
+        );
+
+        match (report.result, report.failure.as_ref()) {
+            (TestResult::Passed, _) => match report.benchmark.as_ref() {
+                Some(statistics) => {
+                    serial_println!(
+                        "><system-out>bench: {}</system-out></testcase>",
+                        Bench(statistics)
+                    );
+                }
+                None => {
+                    serial_println!("/>");
+                }
+            },
+            (TestResult::Ignored, _) => {
+                serial_println!("><skipped/></testcase>");
+            }
+            (result, failure) => {
+                let (message, location) = failure.map_or(("unknown failure", ""), |failure| {
+                    (failure.message.as_str(), failure.location.as_str())
+                });
+
+                let kind = match result {
+                    TestResult::TimedOut => "timeout",
+                    _ => "panic",
+                };
+
+                serial_println!(">");
+                let backtrace = failure.and_then(|failure| failure.backtrace.as_ref());
+
+                serial_println!(
+                    "      <failure type=\"{}\" message=\"{}\">{}{}</failure>",
+                    kind,
+                    Xml(message),
+                    Xml(location),
+                    XmlFrames(backtrace)
+                );
+                serial_println!("    </testcase>");
+            }
+        }
+    }
+
+    fn finish(_summary: &Summary) {
+        serial_println!("  </testsuite>");
+        serial_println!("</testsuites>");
+    }
+
+    fn bail_out(info: &core::panic::PanicInfo, backtrace: &Backtrace) {
+        let mut message = FixedString::<512>::new();
+        fmt::Write::write_fmt(&mut message, format_args!("{}", info)).ok();
+
+        serial_println!(
+            "    <system-err>{}{}</system-err>",
+            Xml(message.as_str()),
+            XmlFrames(Some(backtrace))
+        );
+        Self::finish(&Summary::new());
+    }
+}
+
+fn print_backtrace(failure: &Failure) {
+    if let Some(backtrace) = failure.backtrace.as_ref() {
+        serial_println!("{}", backtrace);
+    }
+}
+
+fn split_name(name: &str) -> (&str, &str) {
+    name.rsplit_once("::").unwrap_or(("", name))
+}
+
+struct Milliseconds(u64);
+
+impl fmt::Display for Milliseconds {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}.{:03}", self.0 / 1_000, self.0 % 1_000)
+    }
+}
+
+struct Seconds(u64);
+
+impl fmt::Display for Seconds {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{}.{:06}",
+            self.0 / 1_000_000,
+            self.0 % 1_000_000
+        )
+    }
+}
+
+struct Bench<'a>(&'a Statistics);
+
+impl fmt::Display for Bench<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{} ns/iter (median {}, +/- {})",
+            self.0.mean, self.0.median, self.0.deviation
+        )?;
+
+        match self.0.change() {
+            Some(change) => write!(
+                formatter,
+                " [{}{}.{}% vs baseline]",
+                if change < 0 { '-' } else { '+' },
+                change.unsigned_abs() / 10,
+                change.unsigned_abs() % 10
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+struct Xml<'a>(&'a str);
+
+impl fmt::Display for Xml<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for character in self.0.chars() {
+            match character {
+                '&' => formatter.write_str("&amp;")?,
+                '<' => formatter.write_str("&lt;")?,
+                '>' => formatter.write_str("&gt;")?,
+                '"' => formatter.write_str("&quot;")?,
+                '\'' => formatter.write_str("&apos;")?,
+                '\n' => formatter.write_str("&#10;")?,
+                _ => fmt::Write::write_char(formatter, character)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Escaped frames, each on its own line.
+struct XmlFrames<'a>(Option<&'a Backtrace>);
+
+impl fmt::Display for XmlFrames<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let mut line = FixedString::<256>::new();
+
+        for frame in self.0.iter().flat_map(|backtrace| backtrace.frames()) {
+            line.clear();
+            fmt::Write::write_fmt(&mut line, format_args!("\n{}", frame)).ok();
+            write!(formatter, "{}", Xml(line.as_str()))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+    use core::fmt::Write;
+
+    #[kernel_test]
+    fn test_split_name() {
+        assert_eq!(
+            split_name("ferros::libs::buffer::tests::test_new"),
+            ("ferros::libs::buffer::tests", "test_new")
+        );
+        assert_eq!(split_name("test_panic"), ("", "test_panic"));
+    }
+
+    #[kernel_test]
+    fn test_durations() {
+        let mut text = FixedString::<32>::new();
+        write!(text, "{} {}", Milliseconds(12_345), Seconds(1_002_003)).unwrap();
+
+        assert_eq!(text.as_str(), "12.345 1.002003");
+    }
+
+    #[kernel_test]
+    fn test_bench() {
+        let mut statistics = Statistics::from_samples(&mut [1_210, 1_190, 1_200], 3);
+
+        let mut text = FixedString::<64>::new();
+        write!(text, "{}", Bench(&statistics)).unwrap();
+        assert_eq!(text.as_str(), "1200 ns/iter (median 1200, +/- 8)");
+
+        statistics.baseline = Some(1_250);
+        text.clear();
+        write!(text, "{}", Bench(&statistics)).unwrap();
+        assert_eq!(
+            text.as_str(),
+            "1200 ns/iter (median 1200, +/- 8) [-4.0% vs baseline]"
+        );
+    }
+
+    #[kernel_test]
+    fn test_xml_escape() {
+        let mut text = FixedString::<64>::new();
+        write!(text, "{}", Xml("a < b && \"c\"\n")).unwrap();
+
+        assert_eq!(text.as_str(), "a &lt; b &amp;&amp; &quot;c&quot;&#10;");
+    }
+}
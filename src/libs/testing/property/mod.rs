@@ -0,0 +1,172 @@
+mod rng;
+mod strategy;
+
+pub use rng::Rng;
+pub use strategy::{array, grid, Array, Bool, Grid, Just, Strategy};
+
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+const DEFAULT_CASES: u32 = 256;
+const DEFAULT_SHRINK_STEPS: u32 = 1024;
+
+static SEED: AtomicU64 = AtomicU64::new(0);
+static SEED_FIXED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    cases: u32,
+    shrink_steps: u32,
+    seed: Option<u64>,
+}
+
+impl Config {
+    pub const fn new() -> Self {
+        Self {
+            cases: DEFAULT_CASES,
+            shrink_steps: DEFAULT_SHRINK_STEPS,
+            seed: None,
+        }
+    }
+
+    pub const fn cases(mut self, cases: u32) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    pub const fn shrink_steps(mut self, shrink_steps: u32) -> Self {
+        self.shrink_steps = shrink_steps;
+        self
+    }
+
+    pub const fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(super) fn set_seed(seed: Option<u64>) {
+    if let Some(seed) = seed {
+        SEED.store(seed, Ordering::Relaxed);
+    }
+
+    SEED_FIXED.store(seed.is_some(), Ordering::Relaxed);
+}
+
+pub fn check<S, F>(strategy: &S, property: F)
+where
+    S: Strategy,
+    F: Fn(S::Value) -> bool,
+{
+    check_with(&Config::new(), strategy, property)
+}
+
+pub fn check_with<S, F>(config: &Config, strategy: &S, property: F)
+where
+    S: Strategy,
+    F: Fn(S::Value) -> bool,
+{
+    let seed = config.seed.unwrap_or_else(next_seed);
+    let mut rng = Rng::new(seed);
+
+    for case in 0..config.cases {
+        let value = strategy.generate(&mut rng);
+
+        if property(value.clone()) {
+            continue;
+        }
+
+        let (minimal, steps) = shrink(strategy, value, &property, config.shrink_steps);
+
+        panic!(
+            "property failed at case {} with seed={:#x}; minimal input after {} shrink steps: {:?}",
+            case, seed, steps, minimal
+        );
+    }
+}
+
+fn shrink<S, F>(strategy: &S, value: S::Value, property: &F, limit: u32) -> (S::Value, u32)
+where
+    S: Strategy,
+    F: Fn(S::Value) -> bool,
+{
+    let mut current = value;
+    let mut steps = 0;
+    let mut candidate = 0;
+
+    while steps < limit {
+        let shrunk = match strategy.shrink(&current, candidate) {
+            Some(shrunk) => shrunk,
+            None => break,
+        };
+
+        match property(shrunk.clone()) {
+            true => candidate += 1,
+            false => {
+                current = shrunk;
+                candidate = 0;
+                steps += 1;
+            }
+        }
+    }
+
+    (current, steps)
+}
+
+fn next_seed() -> u64 {
+    match SEED_FIXED.load(Ordering::Relaxed) {
+        true => SEED.load(Ordering::Relaxed),
+        false => Rng::new(crate::nucleus::clock::timestamp()).next_u64(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_passing_property() {
+        check(&(0..100u32, 0..100u32), |(a, b)| a + b == b + a);
+    }
+
+    #[kernel_test]
+    fn test_shrinks_to_minimal_input() {
+        let property = |value: u32| value < 42;
+        let (minimal, steps) = shrink(&(0..1000u32), 999, &property, 64);
+
+        assert_eq!(minimal, 42);
+        assert!(steps > 0);
+
+        let property = |value: [u8; 4]| value.iter().map(|x| *x as u32).sum::<u32>() < 10;
+        let (minimal, _) = shrink(&array(0..=255u8), [200, 3, 90, 7], &property, 1024);
+
+        assert!(!property(minimal));
+        for index in (0..4).filter(|index| minimal[*index] > 0) {
+            let mut smaller = minimal;
+            smaller[index] -= 1;
+            assert!(property(smaller));
+        }
+    }
+
+    #[kernel_test]
+    fn test_seed_reproduces_cases() {
+        let generate = |seed| {
+            let mut rng = Rng::new(seed);
+            core::array::from_fn::<_, 8, _>(|_| (0..1000u32).generate(&mut rng))
+        };
+
+        assert_eq!(generate(0x5eed), generate(0x5eed));
+        assert_ne!(generate(0x5eed), generate(0x5eee));
+    }
+
+    #[kernel_test(should_panic(expected = "with seed=0x2a; minimal input after"))]
+    fn test_failure_reports_seed() {
+        check_with(&Config::new().seed(42), &(0..100u32), |value| value < 50);
+    }
+}
@@ -0,0 +1,290 @@
+use super::rng::Rng;
+use crate::libs::buffer::GridBuffer;
+use core::fmt::Debug;
+use core::ops::{Range, RangeInclusive};
+
+const EDGE_CHANCE: (u64, u64) = (1, 8);
+
+pub trait Strategy {
+    type Value: Clone + Debug;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value;
+
+    /// Returns the `candidate`th simpler value, or `None` once the candidates are exhausted.
+    fn shrink(&self, value: &Self::Value, candidate: usize) -> Option<Self::Value>;
+}
+
+pub struct Just<T>(pub T);
+
+impl<T: Clone + Debug> Strategy for Just<T> {
+    type Value = T;
+
+    fn generate(&self, _rng: &mut Rng) -> T {
+        self.0.clone()
+    }
+
+    fn shrink(&self, _value: &T, _candidate: usize) -> Option<T> {
+        None
+    }
+}
+
+pub struct Bool;
+
+impl Strategy for Bool {
+    type Value = bool;
+
+    fn generate(&self, rng: &mut Rng) -> bool {
+        rng.chance(1, 2)
+    }
+
+    fn shrink(&self, value: &bool, candidate: usize) -> Option<bool> {
+        match (*value, candidate) {
+            (true, 0) => Some(false),
+            _ => None,
+        }
+    }
+}
+
+fn generate_integer(rng: &mut Rng, low: i128, high: i128) -> i128 {
+    assert!(low <= high, "cannot generate a value from an empty range");
+
+    if rng.chance(EDGE_CHANCE.0, EDGE_CHANCE.1) {
+        return match rng.below(3) {
+            0 => low,
+            1 => high,
+            _ => origin(low, high),
+        };
+    }
+
+    low + rng.below((high - low) as u128 + 1) as i128
+}
+
+fn shrink_integer(low: i128, high: i128, value: i128, candidate: usize) -> Option<i128> {
+    let origin = origin(low, high);
+    let distance = value - origin;
+
+    if distance == 0 {
+        return None;
+    }
+
+    match candidate {
+        0 => Some(origin),
+        _ if candidate >= 128 => None,
+        _ => match distance / (1 << candidate) {
+            0 => None,
+            step => Some(value - step),
+        },
+    }
+}
+
+fn origin(low: i128, high: i128) -> i128 {
+    core::cmp::min(core::cmp::max(0, low), high)
+}
+
+macro_rules! integer_strategy {
+    ($($integer:ty),*) => {$(
+        impl Strategy for Range<$integer> {
+            type Value = $integer;
+
+            fn generate(&self, rng: &mut Rng) -> $integer {
+                generate_integer(rng, self.start as i128, self.end as i128 - 1) as $integer
+            }
+
+            fn shrink(&self, value: &$integer, candidate: usize) -> Option<$integer> {
+                shrink_integer(self.start as i128, self.end as i128 - 1, *value as i128, candidate)
+                    .map(|value| value as $integer)
+            }
+        }
+
+        impl Strategy for RangeInclusive<$integer> {
+            type Value = $integer;
+
+            fn generate(&self, rng: &mut Rng) -> $integer {
+                generate_integer(rng, *self.start() as i128, *self.end() as i128) as $integer
+            }
+
+            fn shrink(&self, value: &$integer, candidate: usize) -> Option<$integer> {
+                shrink_integer(*self.start() as i128, *self.end() as i128, *value as i128, candidate)
+                    .map(|value| value as $integer)
+            }
+        }
+    )*};
+}
+
+integer_strategy!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Shrinks one component of a composite value. Candidates are numbered across all components in
+/// order, so `candidate` is reduced by the component's candidate count when it lies past them.
+fn shrink_component<S: Strategy>(
+    strategy: &S,
+    value: &S::Value,
+    candidate: &mut usize,
+) -> Option<S::Value> {
+    if let Some(shrunk) = strategy.shrink(value, *candidate) {
+        return Some(shrunk);
+    }
+
+    let count = (0..*candidate)
+        .find(|index| strategy.shrink(value, *index).is_none())
+        .unwrap_or(*candidate);
+    *candidate -= count;
+    None
+}
+
+impl<A: Strategy, B: Strategy> Strategy for (A, B) {
+    type Value = (A::Value, B::Value);
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value {
+        (self.0.generate(rng), self.1.generate(rng))
+    }
+
+    fn shrink(&self, value: &Self::Value, mut candidate: usize) -> Option<Self::Value> {
+        if let Some(first) = shrink_component(&self.0, &value.0, &mut candidate) {
+            return Some((first, value.1.clone()));
+        }
+
+        self.1
+            .shrink(&value.1, candidate)
+            .map(|second| (value.0.clone(), second))
+    }
+}
+
+impl<A: Strategy, B: Strategy, C: Strategy> Strategy for (A, B, C) {
+    type Value = (A::Value, B::Value, C::Value);
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value {
+        (
+            self.0.generate(rng),
+            self.1.generate(rng),
+            self.2.generate(rng),
+        )
+    }
+
+    fn shrink(&self, value: &Self::Value, mut candidate: usize) -> Option<Self::Value> {
+        if let Some(first) = shrink_component(&self.0, &value.0, &mut candidate) {
+            return Some((first, value.1.clone(), value.2.clone()));
+        }
+
+        if let Some(second) = shrink_component(&self.1, &value.1, &mut candidate) {
+            return Some((value.0.clone(), second, value.2.clone()));
+        }
+
+        self.2
+            .shrink(&value.2, candidate)
+            .map(|third| (value.0.clone(), value.1.clone(), third))
+    }
+}
+
+pub struct Array<S, const N: usize>(S);
+
+pub fn array<S: Strategy, const N: usize>(element: S) -> Array<S, N> {
+    Array(element)
+}
+
+impl<S: Strategy, const N: usize> Strategy for Array<S, N> {
+    type Value = [S::Value; N];
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value {
+        core::array::from_fn(|_| self.0.generate(rng))
+    }
+
+    fn shrink(&self, value: &Self::Value, mut candidate: usize) -> Option<Self::Value> {
+        for (index, element) in value.iter().enumerate() {
+            if let Some(shrunk) = shrink_component(&self.0, element, &mut candidate) {
+                let mut value = value.clone();
+                value[index] = shrunk;
+                return Some(value);
+            }
+        }
+
+        None
+    }
+}
+
+pub struct Grid<S, const WIDTH: usize, const HEIGHT: usize>(S);
+
+pub fn grid<S: Strategy, const WIDTH: usize, const HEIGHT: usize>(
+    element: S,
+) -> Grid<S, WIDTH, HEIGHT> {
+    Grid(element)
+}
+
+impl<S: Strategy, const WIDTH: usize, const HEIGHT: usize> Strategy for Grid<S, WIDTH, HEIGHT> {
+    type Value = GridBuffer<S::Value, WIDTH, HEIGHT>;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value {
+        GridBuffer {
+            content: core::array::from_fn(|_| core::array::from_fn(|_| self.0.generate(rng))),
+        }
+    }
+
+    fn shrink(&self, value: &Self::Value, mut candidate: usize) -> Option<Self::Value> {
+        for (line, row) in value.content.iter().enumerate() {
+            for (column, cell) in row.iter().enumerate() {
+                if let Some(shrunk) = shrink_component(&self.0, cell, &mut candidate) {
+                    let mut value = value.clone();
+                    value.content[line][column] = shrunk;
+                    return Some(value);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    fn candidates<S: Strategy>(strategy: &S, value: &S::Value) -> usize {
+        (0..)
+            .take_while(|index| strategy.shrink(value, *index).is_some())
+            .count()
+    }
+
+    #[kernel_test]
+    fn test_integer_generate_in_range() {
+        let mut rng = Rng::new(3);
+
+        for _ in 0..256 {
+            assert!((-5..7isize).contains(&(-5..7isize).generate(&mut rng)));
+            assert!((10..=12u8).contains(&(10..=12u8).generate(&mut rng)));
+            (i64::MIN..=i64::MAX).generate(&mut rng);
+        }
+    }
+
+    #[kernel_test]
+    fn test_integer_shrink() {
+        assert_eq!((0..100u32).shrink(&8, 0), Some(0));
+        assert_eq!((0..100u32).shrink(&8, 1), Some(4));
+        assert_eq!((0..100u32).shrink(&8, 3), Some(7));
+        assert_eq!((0..100u32).shrink(&8, 4), None);
+        assert_eq!((-10..-2i32).shrink(&-6, 0), Some(-3));
+        assert_eq!((-10..10i32).shrink(&-6, 2), Some(-5));
+        assert_eq!((0..100u32).shrink(&0, 0), None);
+    }
+
+    #[kernel_test]
+    fn test_composite_shrink() {
+        let pair = (0..10u8, 0..10u8);
+
+        assert_eq!(pair.shrink(&(2, 4), 0), Some((0, 4)));
+        assert_eq!(pair.shrink(&(2, 4), 1), Some((1, 4)));
+        assert_eq!(pair.shrink(&(2, 4), 2), Some((2, 0)));
+        assert_eq!(candidates(&pair, &(2, 4)), 5);
+
+        let array = array::<_, 3>(0..10u8);
+        assert_eq!(array.shrink(&[0, 1, 2], 1), Some([0, 1, 0]));
+        assert_eq!(candidates(&array, &[0, 1, 2]), 3);
+
+        let grid = grid::<_, 2, 2>(Bool);
+        let value = GridBuffer::from_array([[false, true], [true, false]]);
+        assert_eq!(
+            grid.shrink(&value, 1),
+            Some(GridBuffer::from_array([[false, true], [false, false]]))
+        );
+        assert_eq!(grid.shrink(&value, 2), None);
+    }
+}
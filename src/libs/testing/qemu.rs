@@ -1,6 +1,7 @@
 enum QEMUExitCodes {
     Success = 0x10,
     Failure = 0x11,
+    Timeout = 0x12,
 }
 
 const QEMU_PORT: u16 = 0xf4;
@@ -13,6 +14,10 @@ pub fn fail() {
     exit(QEMUExitCodes::Failure);
 }
 
+pub fn timeout() {
+    exit(QEMUExitCodes::Timeout);
+}
+
 fn exit(exit_code: QEMUExitCodes) {
     unsafe {
         let mut port = x86_64::instructions::port::Port::new(QEMU_PORT);
@@ -0,0 +1,305 @@
+use super::{bench, context, traits, watchdog};
+use crate::driver::serial;
+use crate::libs::backtrace::Backtrace;
+use crate::libs::format::FixedString;
+use crate::nucleus::clock;
+use crate::serial_println;
+use core::fmt::Write;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+const TEST_STACK_SIZE: usize = 64 * 1024;
+const MAX_REPORTED_FAILURES: usize = 32;
+
+#[repr(C, align(16))]
+struct TestStack([u8; TEST_STACK_SIZE]);
+
+static mut TEST_STACK: TestStack = TestStack([0; TEST_STACK_SIZE]);
+
+static EXPECTATION: spin::Mutex<Option<traits::Expectation>> = spin::Mutex::new(None);
+static FAILURE: spin::Mutex<Option<Failure>> = spin::Mutex::new(None);
+static TIMEOUT: AtomicU64 = AtomicU64::new(0);
+static TIMED_OUT: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestResult {
+    Passed,
+    Failed,
+    TimedOut,
+    Ignored,
+}
+
+#[derive(Clone, Copy)]
+pub struct Failure {
+    pub message: FixedString<512>,
+    pub location: FixedString<128>,
+    pub backtrace: Option<Backtrace>,
+}
+
+impl Failure {
+    fn new(message: core::fmt::Arguments) -> Self {
+        let mut failure = Self {
+            message: FixedString::new(),
+            location: FixedString::new(),
+            backtrace: None,
+        };
+
+        write!(failure.message, "{}", message).ok();
+        failure
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct TestReport {
+    pub result: TestResult,
+    pub duration: u64,
+    pub failure: Option<Failure>,
+    pub benchmark: Option<bench::Statistics>,
+}
+
+impl TestReport {
+    pub const fn ignored() -> Self {
+        Self {
+            result: TestResult::Ignored,
+            duration: 0,
+            failure: None,
+            benchmark: None,
+        }
+    }
+}
+
+pub struct Summary {
+    passed: usize,
+    failed: usize,
+    ignored: usize,
+    filtered: usize,
+    failures: [&'static str; MAX_REPORTED_FAILURES],
+}
+
+impl Summary {
+    pub const fn new() -> Self {
+        Self {
+            passed: 0,
+            failed: 0,
+            ignored: 0,
+            filtered: 0,
+            failures: [""; MAX_REPORTED_FAILURES],
+        }
+    }
+
+    pub fn record(&mut self, name: &'static str, result: TestResult) {
+        match result {
+            TestResult::Passed => self.passed += 1,
+            TestResult::Ignored => self.ignored += 1,
+            TestResult::Failed | TestResult::TimedOut => {
+                if self.failed < MAX_REPORTED_FAILURES {
+                    self.failures[self.failed] = name;
+                }
+                self.failed += 1;
+            }
+        }
+    }
+
+    pub fn filter_out(&mut self, count: usize) {
+        self.filtered += count;
+    }
+
+    pub fn passed(&self) -> usize {
+        self.passed
+    }
+
+    pub fn failed(&self) -> usize {
+        self.failed
+    }
+
+    pub fn ignored(&self) -> usize {
+        self.ignored
+    }
+
+    pub fn filtered(&self) -> usize {
+        self.filtered
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.failed == 0
+    }
+
+    pub fn report(&self) {
+        if !self.is_success() {
+            serial_println!("\nFailures:");
+
+            for name in self.failures.iter().take(self.failed) {
+                serial_println!("    {}", name);
+            }
+
+            if self.failed > MAX_REPORTED_FAILURES {
+                serial_println!("    ... and {} more", self.failed - MAX_REPORTED_FAILURES);
+            }
+        }
+
+        serial_println!(
+            "\nTest result: {}. {} passed; {} failed; {} ignored; {} filtered out\n",
+            if self.is_success() { "ok" } else { "FAILED" },
+            self.passed,
+            self.failed,
+            self.ignored,
+            self.filtered
+        );
+    }
+}
+
+impl Default for Summary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(super) fn run_test<F>(
+    expectation: traits::Expectation,
+    timeout: Option<u64>,
+    function: F,
+) -> TestReport
+where
+    F: FnOnce(),
+{
+    let timeout = timeout.unwrap_or(watchdog::DEFAULT_TIMEOUT);
+
+    *EXPECTATION.lock() = Some(expectation);
+    *FAILURE.lock() = None;
+    TIMEOUT.store(timeout, Ordering::SeqCst);
+    TIMED_OUT.store(false, Ordering::SeqCst);
+
+    let stack = unsafe { &mut (*core::ptr::addr_of_mut!(TEST_STACK)).0 };
+
+    let start = clock::timestamp();
+    let outcome = context::run_guarded_on_stack(stack, || {
+        watchdog::arm(timeout);
+        function();
+        watchdog::disarm();
+    });
+    let duration = clock::cycles_to_microseconds(clock::timestamp() - start);
+
+    watchdog::disarm();
+    x86_64::instructions::interrupts::enable();
+
+    *EXPECTATION.lock() = None;
+
+    if outcome == context::Outcome::Panicked {
+        recover_locks();
+    }
+
+    let failure = match (expectation, outcome) {
+        (_, context::Outcome::Panicked) => FAILURE.lock().take(),
+        (traits::Expectation::Panic(_), context::Outcome::Returned) => {
+            Some(Failure::new(format_args!("test did not panic")))
+        }
+        _ => None,
+    };
+
+    TestReport {
+        result: match failure {
+            Some(_) if TIMED_OUT.load(Ordering::SeqCst) => TestResult::TimedOut,
+            Some(_) => TestResult::Failed,
+            None => TestResult::Passed,
+        },
+        duration,
+        failure,
+        benchmark: None,
+    }
+}
+
+pub(super) fn handle_panic(info: &core::panic::PanicInfo, backtrace: &Backtrace) {
+    let expectation = *EXPECTATION.lock();
+
+    let expectation = match expectation {
+        Some(expectation) if context::is_guarded() => expectation,
+        _ => return,
+    };
+
+    watchdog::disarm();
+
+    if let traits::Expectation::Panic(expected) = expectation {
+        if panic_message_matches(info, expected) {
+            context::resume();
+        }
+    }
+
+    let mut failure = match expectation {
+        traits::Expectation::Panic(Some(expected)) => Failure::new(format_args!(
+            "expected panic containing {:?}, got: {}",
+            expected,
+            info.message()
+        )),
+        _ => Failure::new(format_args!("{}", info.message())),
+    };
+
+    if let Some(location) = info.location() {
+        write!(failure.location, "{}", location).ok();
+    }
+
+    failure.backtrace = Some(*backtrace);
+
+    *FAILURE.lock() = Some(failure);
+    context::resume();
+}
+
+pub(super) fn handle_timeout() {
+    if !context::is_guarded() {
+        return;
+    }
+
+    let timeout = TIMEOUT.load(Ordering::SeqCst);
+
+    *FAILURE.lock() = Some(Failure::new(format_args!(
+        "test timed out after {} ms",
+        timeout
+    )));
+    TIMED_OUT.store(true, Ordering::SeqCst);
+
+    context::resume();
+}
+
+fn panic_message_matches(info: &core::panic::PanicInfo, expected: Option<&str>) -> bool {
+    let expected = match expected {
+        Some(expected) => expected,
+        None => return true,
+    };
+
+    let mut message = FixedString::<512>::new();
+    write!(message, "{}", info.message()).ok();
+
+    message.as_str().contains(expected)
+}
+
+fn recover_locks() {
+    unsafe {
+        crate::driver::vga::VGA_WRITER.force_unlock();
+        serial::force_unlock(serial::CONSOLE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_summary() {
+        let mut summary = Summary::new();
+
+        summary.record("first", TestResult::Passed);
+        summary.record("second", TestResult::Failed);
+        summary.record("third", TestResult::Ignored);
+
+        assert!(!summary.is_success());
+        assert_eq!((summary.passed, summary.failed, summary.ignored), (1, 1, 1));
+        assert_eq!(summary.failures[0], "second");
+    }
+
+    #[kernel_test]
+    fn test_failure() {
+        let failure = Failure::new(format_args!("value was {}", 42));
+
+        assert_eq!(failure.message.as_str(), "value was 42");
+        assert!(failure.location.is_empty());
+    }
+}
@@ -0,0 +1,57 @@
+use crate::driver::vga::{self, Snapshot};
+use crate::{serial_print, serial_println};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+const SNAPSHOT_DIRECTORY: &str = "snapshots";
+const SNAPSHOT_EXTENSION: &str = "snap";
+
+static UPDATE: AtomicBool = AtomicBool::new(false);
+
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:literal) => {
+        $crate::libs::testing::check_snapshot(
+            file!(),
+            $name,
+            include_str!(concat!("snapshots/", $name, ".snap")),
+        )
+    };
+}
+
+pub(super) fn set_update_mode(update: bool) {
+    UPDATE.store(update, Ordering::SeqCst);
+}
+
+pub fn check_snapshot(source: &str, name: &str, stored: &str) {
+    let actual = vga::snapshot().expect("snapshot taken while the screen is being printed to");
+    let expected = Snapshot::parse(stored);
+
+    if expected.as_ref() == Some(&actual) {
+        return;
+    }
+
+    let directory = source
+        .rsplit_once('/')
+        .map_or("", |(directory, _)| directory);
+
+    if UPDATE.load(Ordering::SeqCst) {
+        serial_println!(
+            "\n--- snapshot {}/{}/{}.{} ---",
+            directory,
+            SNAPSHOT_DIRECTORY,
+            name,
+            SNAPSHOT_EXTENSION
+        );
+        serial_print!("{}", actual);
+        serial_println!("--- end snapshot ---");
+        return;
+    }
+
+    serial_println!("\nsnapshot `{}` differs from the stored version:", name);
+    serial_print!("{}", actual.diff(expected.as_ref()));
+
+    panic!(
+        "snapshot `{}` does not match {}/{}/{}.{}",
+        name, directory, SNAPSHOT_DIRECTORY, name, SNAPSHOT_EXTENSION
+    );
+}
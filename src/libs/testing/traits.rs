@@ -1,26 +1,165 @@
+use super::runner;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expectation {
+    Success,
+    Panic(Option<&'static str>),
+}
+
 pub trait Testable {
-    fn run(&self) -> ();
+    fn run(&self) -> runner::TestReport;
     fn should_panic(&self) -> bool;
+
+    fn name(&self) -> &'static str;
+
+    fn ignored(&self) -> bool {
+        self.name().ends_with("__ignore")
+    }
+
+    fn timeout(&self) -> Option<u64> {
+        None
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn benchmark(&self) -> bool {
+        false
+    }
+
+    fn expectation(&self) -> Expectation {
+        match self.should_panic() {
+            true => Expectation::Panic(None),
+            false => Expectation::Success,
+        }
+    }
 }
 
 impl<T> Testable for T
 where
     T: Fn(),
 {
-    fn run(&self) {
-        super::serial::serial_print!("{}.........\t", core::any::type_name::<T>());
+    fn run(&self) -> runner::TestReport {
+        runner::run_test(self.expectation(), self.timeout(), self)
+    }
+
+    fn should_panic(&self) -> bool {
+        self.name().ends_with("__should_panic")
+    }
+
+    fn name(&self) -> &'static str {
+        core::any::type_name::<T>()
+    }
+}
+
+pub struct ShouldPanic {
+    name: &'static str,
+    function: fn(),
+    expected: Option<&'static str>,
+}
+
+impl ShouldPanic {
+    pub const fn new(name: &'static str, function: fn()) -> Self {
+        Self {
+            name,
+            function,
+            expected: None,
+        }
+    }
+
+    pub const fn expected(mut self, message: &'static str) -> Self {
+        self.expected = Some(message);
+        self
+    }
+}
+
+impl Testable for ShouldPanic {
+    fn run(&self) -> runner::TestReport {
+        runner::run_test(self.expectation(), self.timeout(), self.function)
+    }
+
+    fn should_panic(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn expectation(&self) -> Expectation {
+        Expectation::Panic(self.expected)
+    }
+}
+
+pub struct KernelTest {
+    name: &'static str,
+    function: fn(),
+    expectation: Expectation,
+    ignored: bool,
+    timeout: Option<u64>,
+    tags: &'static [&'static str],
+}
 
-        if !self.should_panic() {
-            self();
-            super::serial::serial_println!("[ok]");
-            return;
+impl KernelTest {
+    pub const fn new(name: &'static str, function: fn()) -> Self {
+        Self {
+            name,
+            function,
+            expectation: Expectation::Success,
+            ignored: false,
+            timeout: None,
+            tags: &[],
         }
+    }
+
+    pub const fn ignore(mut self) -> Self {
+        self.ignored = true;
+        self
+    }
+
+    pub const fn should_panic(mut self, expected: Option<&'static str>) -> Self {
+        self.expectation = Expectation::Panic(expected);
+        self
+    }
 
-        super::serial::serial_println!("[ok]");
+    pub const fn timeout(mut self, milliseconds: u64) -> Self {
+        self.timeout = Some(milliseconds);
+        self
+    }
+
+    pub const fn tags(mut self, tags: &'static [&'static str]) -> Self {
+        self.tags = tags;
+        self
+    }
+}
+
+impl Testable for KernelTest {
+    fn run(&self) -> runner::TestReport {
+        runner::run_test(self.expectation, self.timeout, self.function)
     }
 
     fn should_panic(&self) -> bool {
-        let func_name = core::any::type_name::<T>();
-        func_name.ends_with("__should_panic")
+        self.expectation != Expectation::Success
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn ignored(&self) -> bool {
+        self.ignored
+    }
+
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        self.tags
+    }
+
+    fn expectation(&self) -> Expectation {
+        self.expectation
     }
 }
@@ -0,0 +1,49 @@
+use super::runner;
+use crate::nucleus::interrupt::{self, pic, timer};
+use core::sync::atomic::{AtomicU64, Ordering};
+
+const TICK_FREQUENCY: u32 = 1_000;
+pub(super) const DEFAULT_TIMEOUT: u64 = 5_000;
+
+static DEADLINE: AtomicU64 = AtomicU64::new(0);
+
+pub(super) fn init() {
+    interrupt::init_idt();
+    pic::init(1 << timer::IRQ);
+    timer::init(TICK_FREQUENCY);
+    timer::set_handler(Some(tick));
+
+    x86_64::instructions::interrupts::enable();
+}
+
+pub(super) fn arm(milliseconds: u64) {
+    let deadline = timer::ticks() + timer::milliseconds_to_ticks(milliseconds);
+    DEADLINE.store(deadline, Ordering::SeqCst);
+}
+
+pub(super) fn disarm() {
+    DEADLINE.store(0, Ordering::SeqCst);
+}
+
+fn tick(ticks: u64) {
+    let deadline = DEADLINE.load(Ordering::SeqCst);
+
+    if deadline != 0 && ticks >= deadline {
+        disarm();
+        runner::handle_timeout();
+    }
+}
+
+#[cfg(all(test, target_os = "none"))]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_arm() {
+        arm(DEFAULT_TIMEOUT);
+
+        let deadline = DEADLINE.load(Ordering::SeqCst);
+        assert!(deadline > timer::ticks());
+        assert!(deadline <= timer::ticks() + timer::milliseconds_to_ticks(DEFAULT_TIMEOUT));
+    }
+}
@@ -7,7 +7,11 @@
 use ferros::println;
 
 #[no_mangle]
-pub extern "C" fn _start() -> ! {
+pub extern "C" fn _start(boot_info: &'static bootloader::BootInfo) -> ! {
+    // A frame allocator must exclude these frames.
+    let _dmesg_frames = ferros::libs::dmesg::init(boot_info);
+    ferros::libs::logger::init().ok();
+
     // ferros::init();
 
     // x86_64::instructions::interrupts::int3();
@@ -48,7 +52,10 @@ fn divide_by_zero() {
 #[cfg(not(test))]
 #[panic_handler]
 fn panic(info: &core::panic::PanicInfo) -> ! {
-    println!("{}", info);
+    ferros::emergency_println!("{}", info);
+    ferros::emergency_print!("{}", ferros::libs::backtrace::Backtrace::capture());
+    ferros::libs::dmesg::dump_to_serial();
+    ferros::nucleus::debug::gdbstub::halt_on_panic();
     loop {}
 }
 
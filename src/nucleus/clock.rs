@@ -0,0 +1,58 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+use x86_64::instructions::port::Port;
+
+const PIT_FREQUENCY: u64 = 1_193_182;
+const PIT_CHANNEL_2: u16 = 0x42;
+const PIT_COMMAND: u16 = 0x43;
+const PIT_GATE: u16 = 0x61;
+
+const CALIBRATION_MICROSECONDS: u64 = 10_000;
+
+static CYCLES_PER_MICROSECOND: AtomicU64 = AtomicU64::new(0);
+
+pub fn timestamp() -> u64 {
+    unsafe { core::arch::x86_64::_rdtsc() }
+}
+
+pub fn cycles_per_microsecond() -> u64 {
+    let cached = CYCLES_PER_MICROSECOND.load(Ordering::Relaxed);
+
+    if cached != 0 {
+        return cached;
+    }
+
+    let calibrated = core::cmp::max(calibrate(), 1);
+    CYCLES_PER_MICROSECOND.store(calibrated, Ordering::Relaxed);
+    calibrated
+}
+
+pub fn cycles_to_microseconds(cycles: u64) -> u64 {
+    cycles / cycles_per_microsecond()
+}
+
+fn calibrate() -> u64 {
+    let count = PIT_FREQUENCY * CALIBRATION_MICROSECONDS / 1_000_000;
+
+    let mut gate = Port::<u8>::new(PIT_GATE);
+    let mut command = Port::<u8>::new(PIT_COMMAND);
+    let mut channel = Port::<u8>::new(PIT_CHANNEL_2);
+
+    unsafe {
+        let value = gate.read();
+        gate.write((value & !0x02) | 0x01);
+
+        command.write(0xb0);
+        channel.write(count as u8);
+        channel.write((count >> 8) as u8);
+
+        let value = gate.read();
+        gate.write(value & !0x01);
+        gate.write(value | 0x01);
+
+        let start = timestamp();
+        while gate.read() & 0x20 == 0 {}
+        let end = timestamp();
+
+        (end - start) / CALIBRATION_MICROSECONDS
+    }
+}
@@ -0,0 +1,331 @@
+// Shared with the host tool in tools/crashdump, so this file only uses `core`.
+
+use core::convert::TryInto;
+use core::fmt;
+
+pub const MAGIC: [u8; 8] = *b"FERROSCD";
+pub const VERSION: u32 = 1;
+pub const HEADER_SIZE: usize = 12;
+pub const RECORD_HEADER_SIZE: usize = 6;
+
+/// Lines around the base64 text of a dump sent over serial, so it can be cut out of a log.
+pub const BEGIN: &str = "-----BEGIN FERROS CRASH DUMP-----";
+pub const END: &str = "-----END FERROS CRASH DUMP-----";
+
+const LINE_GROUPS: usize = 19;
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Order of the little-endian `u64`s in a registers record.
+pub const REGISTER_NAMES: [&str; 26] = [
+    "rax", "rbx", "rcx", "rdx", "rsi", "rdi", "rbp", "rsp", "r8", "r9", "r10", "r11", "r12", "r13",
+    "r14", "r15", "rip", "rflags", "cs", "ss", "vector", "error", "cr0", "cr2", "cr3", "cr4",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u16)]
+pub enum RecordKind {
+    /// UTF-8 description of the fault.
+    Reason = 1,
+    /// `REGISTER_NAMES.len()` registers.
+    Registers = 2,
+    /// Start address as `u64`, then the bytes from the stack pointer up.
+    Stack = 3,
+    /// Entries of return address `u64`, offset into the symbol `u64`, name length `u16` and name.
+    /// An empty name means the symbol is unknown.
+    Backtrace = 4,
+    /// UTF-8 text of the kernel log ring.
+    Log = 5,
+    /// Base address as `u64`, then the loaded table.
+    InterruptTable = 6,
+    /// Base address as `u64`, then the loaded table.
+    GlobalTable = 7,
+    /// Address `u64`, name length `u16`, name, then the bytes. No bytes means it was unmapped.
+    Memory = 8,
+    /// CRC-32 of every byte before this record, as `u32`. Always last.
+    End = 0xffff,
+}
+
+impl RecordKind {
+    pub fn from_u16(kind: u16) -> Option<Self> {
+        let kind = match kind {
+            1 => Self::Reason,
+            2 => Self::Registers,
+            3 => Self::Stack,
+            4 => Self::Backtrace,
+            5 => Self::Log,
+            6 => Self::InterruptTable,
+            7 => Self::GlobalTable,
+            8 => Self::Memory,
+            0xffff => Self::End,
+            _ => return None,
+        };
+
+        Some(kind)
+    }
+}
+
+pub fn header() -> [u8; HEADER_SIZE] {
+    let mut header = [0; HEADER_SIZE];
+    header[..8].copy_from_slice(&MAGIC);
+    header[8..].copy_from_slice(&VERSION.to_le_bytes());
+    header
+}
+
+pub fn record_header(kind: RecordKind, length: u32) -> [u8; RECORD_HEADER_SIZE] {
+    let mut header = [0; RECORD_HEADER_SIZE];
+    header[..2].copy_from_slice(&(kind as u16).to_le_bytes());
+    header[2..].copy_from_slice(&length.to_le_bytes());
+    header
+}
+
+/// CRC-32 as used by zlib and Ethernet.
+#[derive(Debug, Clone, Copy)]
+pub struct Crc32(u32);
+
+impl Crc32 {
+    pub const fn new() -> Self {
+        Self(0xffff_ffff)
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u32;
+
+            for _ in 0..8 {
+                let mask = (self.0 & 1).wrapping_neg();
+                self.0 = (self.0 >> 1) ^ (0xedb8_8320 & mask);
+            }
+        }
+    }
+
+    pub fn finish(self) -> u32 {
+        !self.0
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatError {
+    BadMagic,
+    UnsupportedVersion(u32),
+    Truncated,
+    MissingEnd,
+    ChecksumMismatch { expected: u32, actual: u32 },
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "not a ferros crash dump"),
+            Self::UnsupportedVersion(version) => write!(f, "unsupported version {}", version),
+            Self::Truncated => write!(f, "dump is truncated"),
+            Self::MissingEnd => write!(f, "dump has no end record"),
+            Self::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "checksum mismatch: expected {:08x}, computed {:08x}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Record<'a> {
+    /// Raw, so a newer dump's unknown records can be skipped.
+    pub kind: u16,
+    pub payload: &'a [u8],
+}
+
+/// Checks the header, the record framing and the checksum, and returns the records before the
+/// end record.
+pub fn parse(dump: &[u8]) -> Result<Records<'_>, FormatError> {
+    if dump.len() < HEADER_SIZE || dump[..8] != MAGIC {
+        return Err(FormatError::BadMagic);
+    }
+
+    let version = read_u32(&dump[8..]).ok_or(FormatError::Truncated)?;
+
+    if version != VERSION {
+        return Err(FormatError::UnsupportedVersion(version));
+    }
+
+    let mut records = Records {
+        rest: &dump[HEADER_SIZE..],
+    };
+    let mut offset = HEADER_SIZE;
+
+    loop {
+        let record = records.next_record()?.ok_or(FormatError::MissingEnd)?;
+
+        if record.kind == RecordKind::End as u16 {
+            let expected = read_u32(record.payload).ok_or(FormatError::Truncated)?;
+            let mut crc = Crc32::new();
+            crc.update(&dump[..offset]);
+
+            return match crc.finish() {
+                actual if actual == expected => Ok(Records {
+                    rest: &dump[HEADER_SIZE..offset],
+                }),
+                actual => Err(FormatError::ChecksumMismatch { expected, actual }),
+            };
+        }
+
+        offset += RECORD_HEADER_SIZE + record.payload.len();
+    }
+}
+
+#[derive(Clone)]
+pub struct Records<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> Records<'a> {
+    fn next_record(&mut self) -> Result<Option<Record<'a>>, FormatError> {
+        if self.rest.is_empty() {
+            return Ok(None);
+        }
+
+        if self.rest.len() < RECORD_HEADER_SIZE {
+            return Err(FormatError::Truncated);
+        }
+
+        let kind = read_u16(self.rest).ok_or(FormatError::Truncated)?;
+        let length = read_u32(&self.rest[2..]).ok_or(FormatError::Truncated)? as usize;
+        let rest = &self.rest[RECORD_HEADER_SIZE..];
+
+        if rest.len() < length {
+            return Err(FormatError::Truncated);
+        }
+
+        let (payload, rest) = rest.split_at(length);
+        self.rest = rest;
+
+        Ok(Some(Record { kind, payload }))
+    }
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = Record<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().ok().flatten()
+    }
+}
+
+pub fn read_u16(bytes: &[u8]) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(..2)?.try_into().ok()?))
+}
+
+pub fn read_u32(bytes: &[u8]) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(..4)?.try_into().ok()?))
+}
+
+pub fn read_u64(bytes: &[u8]) -> Option<u64> {
+    Some(u64::from_le_bytes(bytes.get(..8)?.try_into().ok()?))
+}
+
+/// Writes base64 in lines of 76 characters.
+pub struct Base64Encoder<W: fmt::Write> {
+    out: W,
+    pending: [u8; 3],
+    pending_length: usize,
+    groups: usize,
+}
+
+impl<W: fmt::Write> Base64Encoder<W> {
+    pub const fn new(out: W) -> Self {
+        Self {
+            out,
+            pending: [0; 3],
+            pending_length: 0,
+            groups: 0,
+        }
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) -> fmt::Result {
+        for byte in bytes {
+            self.pending[self.pending_length] = *byte;
+            self.pending_length += 1;
+
+            if self.pending_length == 3 {
+                self.flush_group()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pads the last group and ends the last line.
+    pub fn finish(mut self) -> Result<W, fmt::Error> {
+        if self.pending_length > 0 {
+            self.flush_group()?;
+        }
+
+        if self.groups > 0 {
+            self.out.write_char('\n')?;
+        }
+
+        Ok(self.out)
+    }
+
+    fn flush_group(&mut self) -> fmt::Result {
+        let [first, second, third] = self.pending;
+        let length = self.pending_length;
+
+        let indices = [
+            first >> 2,
+            (first & 0x03) << 4 | second >> 4,
+            (second & 0x0f) << 2 | third >> 6,
+            third & 0x3f,
+        ];
+
+        for (position, index) in indices.iter().enumerate() {
+            match position <= length {
+                true => self.out.write_char(ALPHABET[*index as usize] as char)?,
+                false => self.out.write_char('=')?,
+            }
+        }
+
+        self.pending = [0; 3];
+        self.pending_length = 0;
+        self.groups += 1;
+
+        if self.groups == LINE_GROUPS {
+            self.groups = 0;
+            self.out.write_char('\n')?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Decodes base64 into `out`, skipping whitespace. Returns the decoded length, or `None` for
+/// invalid input or a too small buffer.
+pub fn decode_base64(text: &str, out: &mut [u8]) -> Option<usize> {
+    let mut length = 0;
+    let mut bits = 0u32;
+    let mut count = 0;
+
+    for byte in text.bytes().filter(|byte| !byte.is_ascii_whitespace()) {
+        if byte == b'=' {
+            break;
+        }
+
+        let value = ALPHABET.iter().position(|digit| *digit == byte)? as u32;
+        bits = (bits << 6 | value) & 0xffff;
+        count += 6;
+
+        if count >= 8 {
+            count -= 8;
+            *out.get_mut(length)? = (bits >> count) as u8;
+            length += 1;
+        }
+    }
+
+    Some(length)
+}
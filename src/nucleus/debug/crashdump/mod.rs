@@ -0,0 +1,491 @@
+mod format;
+
+pub use format::{
+    decode_base64, parse, read_u16, read_u32, read_u64, Base64Encoder, Crc32, FormatError, Record,
+    RecordKind, Records, BEGIN, END, REGISTER_NAMES,
+};
+
+use super::memory;
+use super::trap::TrapFrame;
+use crate::driver::serial::{self, ComPort, Config, SerialError};
+use crate::libs::backtrace::{self, Backtrace, Frames};
+use crate::libs::dmesg;
+use crate::nucleus::sync::IrqMutex;
+use core::fmt::{self, Write};
+use x86_64::registers::control::{Cr0, Cr2, Cr3, Cr4};
+
+pub const MAX_REGIONS: usize = 8;
+pub const MAX_REGION_SIZE: usize = 64 * 1024;
+
+const STACK_SIZE: usize = 1024;
+const PAGE_SIZE: u64 = 4096;
+
+static SETTINGS: IrqMutex<Settings> = IrqMutex::new(Settings {
+    port: None,
+    regions: [None; MAX_REGIONS],
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrashDumpError {
+    TooManyRegions,
+    RegionTooLarge(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Region {
+    name: &'static str,
+    address: u64,
+    length: usize,
+}
+
+struct Settings {
+    port: Option<ComPort>,
+    regions: [Option<Region>; MAX_REGIONS],
+}
+
+/// Frames the encoded dump and keeps the checksum the end record carries.
+struct Dump<F: FnMut(&[u8])> {
+    sink: F,
+    crc: Crc32,
+}
+
+impl<F: FnMut(&[u8])> Dump<F> {
+    fn new(sink: F) -> Self {
+        let mut dump = Self {
+            sink,
+            crc: Crc32::new(),
+        };
+
+        dump.write(&format::header());
+        dump
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.crc.update(bytes);
+        (self.sink)(bytes);
+    }
+
+    fn record(&mut self, kind: RecordKind, parts: &[&[u8]]) {
+        let length = parts.iter().map(|part| part.len()).sum::<usize>();
+        self.write(&format::record_header(kind, length as u32));
+
+        for part in parts {
+            self.write(part);
+        }
+    }
+
+    fn finish(mut self) {
+        let crc = self.crc.finish();
+
+        (self.sink)(&format::record_header(RecordKind::End, 4));
+        (self.sink)(&crc.to_le_bytes());
+    }
+}
+
+/// Writes text to a serial port, bypassing a lock held by the faulting code.
+struct SerialText(ComPort);
+
+impl Write for SerialText {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        serial::write_fmt(self.0, format_args!("{}", string)).map_err(|_| fmt::Error)
+    }
+}
+
+/// Enables crash dumps on fatal faults, written to `port`. The physical memory offset lets a
+/// dump leave out unmapped stack and regions instead of faulting again.
+pub fn init(port: ComPort, physical_memory_offset: u64) -> Result<(), SerialError> {
+    if serial::with_port(port, |_| ()).is_none() {
+        serial::init(port, Config::new())?;
+    }
+
+    memory::set_physical_memory_offset(physical_memory_offset);
+
+    SETTINGS.lock().port = Some(port);
+    Ok(())
+}
+
+/// Adds memory to every dump, for example a driver's state.
+pub fn add_region(name: &'static str, address: u64, length: usize) -> Result<(), CrashDumpError> {
+    if length > MAX_REGION_SIZE {
+        return Err(CrashDumpError::RegionTooLarge(length));
+    }
+
+    let mut settings = SETTINGS.lock();
+    let slot = settings
+        .regions
+        .iter_mut()
+        .find(|slot| slot.is_none())
+        .ok_or(CrashDumpError::TooManyRegions)?;
+
+    *slot = Some(Region {
+        name,
+        address,
+        length,
+    });
+    Ok(())
+}
+
+pub fn clear_regions() {
+    SETTINGS.lock().regions = [None; MAX_REGIONS];
+}
+
+/// Writes a dump for the fault `frame` describes, as base64 between `BEGIN` and `END` lines.
+/// Does nothing before `init`.
+pub fn write(reason: &str, frame: &TrapFrame) {
+    let (port, regions) = match SETTINGS.try_lock() {
+        Some(settings) => (settings.port, settings.regions),
+        None => return,
+    };
+
+    let port = match port {
+        Some(port) => port,
+        None => return,
+    };
+
+    let mut text = SerialText(port);
+
+    if writeln!(text, "{}", BEGIN).is_err() {
+        return;
+    }
+
+    let mut encoder = Base64Encoder::new(text);
+    let sink = |bytes: &[u8]| {
+        encoder.write(bytes).ok();
+    };
+    collect(sink, reason, frame, &regions);
+
+    if let Ok(mut text) = encoder.finish() {
+        writeln!(text, "{}", END).ok();
+    }
+}
+
+fn collect<F>(sink: F, reason: &str, frame: &TrapFrame, regions: &[Option<Region>])
+where
+    F: FnMut(&[u8]),
+{
+    let mut dump = Dump::new(sink);
+
+    dump.record(RecordKind::Reason, &[reason.as_bytes()]);
+
+    let (frame_address, flags) = Cr3::read_raw();
+    let control = [
+        Cr0::read_raw(),
+        Cr2::read_raw(),
+        frame_address.start_address().as_u64() | flags as u64,
+        Cr4::read_raw(),
+    ];
+    dump.record(RecordKind::Registers, &[&encode_registers(frame, control)]);
+
+    let stack = readable(frame.rsp, STACK_SIZE);
+    dump.record(RecordKind::Stack, &[&frame.rsp.to_le_bytes(), stack]);
+
+    write_backtrace(&mut dump, frame);
+
+    write_log(&mut dump);
+
+    let idt = x86_64::instructions::tables::sidt();
+    let table = readable(idt.base.as_u64(), idt.limit as usize + 1);
+    dump.record(
+        RecordKind::InterruptTable,
+        &[&idt.base.as_u64().to_le_bytes(), table],
+    );
+
+    let gdt = x86_64::instructions::tables::sgdt();
+    let table = readable(gdt.base.as_u64(), gdt.limit as usize + 1);
+    dump.record(
+        RecordKind::GlobalTable,
+        &[&gdt.base.as_u64().to_le_bytes(), table],
+    );
+
+    for region in regions.iter().flatten() {
+        dump.record(
+            RecordKind::Memory,
+            &[
+                &region.address.to_le_bytes(),
+                &(region.name.len() as u16).to_le_bytes(),
+                region.name.as_bytes(),
+                readable(region.address, region.length),
+            ],
+        );
+    }
+
+    dump.finish();
+}
+
+fn encode_registers(frame: &TrapFrame, control: [u64; 4]) -> [u8; REGISTER_NAMES.len() * 8] {
+    let [cr0, cr2, cr3, cr4] = control;
+
+    let registers = [
+        frame.rax,
+        frame.rbx,
+        frame.rcx,
+        frame.rdx,
+        frame.rsi,
+        frame.rdi,
+        frame.rbp,
+        frame.rsp,
+        frame.r8,
+        frame.r9,
+        frame.r10,
+        frame.r11,
+        frame.r12,
+        frame.r13,
+        frame.r14,
+        frame.r15,
+        frame.rip,
+        frame.rflags,
+        frame.cs,
+        frame.ss,
+        frame.vector,
+        frame.error_code,
+        cr0,
+        cr2,
+        cr3,
+        cr4,
+    ];
+
+    let mut bytes = [0; REGISTER_NAMES.len() * 8];
+
+    for (chunk, register) in bytes.chunks_exact_mut(8).zip(registers.iter()) {
+        chunk.copy_from_slice(&register.to_le_bytes());
+    }
+
+    bytes
+}
+
+/// The faulting instruction first, then the return addresses from the frame pointer chain.
+fn write_backtrace<F: FnMut(&[u8])>(dump: &mut Dump<F>, frame: &TrapFrame) {
+    let table = backtrace::embedded();
+    let faulting = backtrace::Frame {
+        address: frame.rip,
+        symbol: table.and_then(|table| table.lookup(frame.rip)),
+    };
+
+    let chain = unsafe { Frames::from_frame_pointer(frame.rbp) }
+        .validated(|frame_pointer| memory::is_mapped(frame_pointer, 16));
+    let callers = Backtrace::from_frames(chain);
+    let frames = || core::iter::once(faulting).chain(callers.frames());
+
+    let length = frames()
+        .map(|frame| 18 + frame.symbol.map_or(0, |symbol| symbol.name.len()))
+        .sum::<usize>();
+
+    dump.write(&format::record_header(RecordKind::Backtrace, length as u32));
+
+    for frame in frames() {
+        let (offset, name) = match frame.symbol {
+            Some(symbol) => (symbol.offset, symbol.name),
+            None => (0, ""),
+        };
+
+        dump.write(&frame.address.to_le_bytes());
+        dump.write(&offset.to_le_bytes());
+        dump.write(&(name.len() as u16).to_le_bytes());
+        dump.write(name.as_bytes());
+    }
+}
+
+fn write_log<F: FnMut(&[u8])>(dump: &mut Dump<F>) {
+    let mut length = 0;
+    dmesg::read_bytes(|bytes| length += bytes.len());
+    dump.write(&format::record_header(RecordKind::Log, length as u32));
+
+    let mut remaining = length;
+
+    dmesg::read_bytes(|bytes| {
+        let part = &bytes[..core::cmp::min(bytes.len(), remaining)];
+        remaining -= part.len();
+        dump.write(part);
+    });
+
+    // A message logged in between can move the start of the ring; keep the announced length.
+    for _ in 0..remaining {
+        dump.write(b"\n");
+    }
+}
+
+/// The mapped start of a range: all of it, the rest of its first page, or nothing.
+fn readable(address: u64, length: usize) -> &'static [u8] {
+    let length = core::cmp::min(length, MAX_REGION_SIZE);
+
+    let length = match memory::is_mapped(address, length) {
+        true => length,
+        false if memory::is_mapped(address, 1) => {
+            core::cmp::min(length as u64, PAGE_SIZE - address % PAGE_SIZE) as usize
+        }
+        false => 0,
+    };
+
+    match length {
+        0 => &[],
+        _ => unsafe { core::slice::from_raw_parts(address as *const u8, length) },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::format::FixedString;
+    use crate::libs::testing::kernel_test;
+
+    struct Buffer {
+        bytes: [u8; 256],
+        length: usize,
+    }
+
+    impl Buffer {
+        fn new() -> Self {
+            Self {
+                bytes: [0; 256],
+                length: 0,
+            }
+        }
+
+        fn push(&mut self, bytes: &[u8]) {
+            self.bytes[self.length..self.length + bytes.len()].copy_from_slice(bytes);
+            self.length += bytes.len();
+        }
+
+        fn as_slice(&self) -> &[u8] {
+            &self.bytes[..self.length]
+        }
+    }
+
+    #[kernel_test]
+    fn test_crc32() {
+        let mut crc = Crc32::new();
+        crc.update(b"1234");
+        crc.update(b"56789");
+
+        assert_eq!(crc.finish(), 0xcbf4_3926);
+    }
+
+    #[kernel_test]
+    fn test_base64() {
+        for (bytes, text) in [
+            (&b"ferros"[..], "ZmVycm9z\n"),
+            (b"f", "Zg==\n"),
+            (b"fe", "ZmU=\n"),
+            (b"", ""),
+        ] {
+            let mut encoder = Base64Encoder::new(FixedString::<16>::new());
+            encoder.write(bytes).unwrap();
+
+            let encoded = encoder.finish().unwrap();
+            assert_eq!(encoded.as_str(), text);
+
+            let mut decoded = [0; 8];
+            let length = decode_base64(encoded.as_str(), &mut decoded).unwrap();
+            assert_eq!(&decoded[..length], bytes);
+        }
+
+        assert_eq!(decode_base64("Zm!=", &mut [0; 8]), None);
+    }
+
+    #[kernel_test]
+    fn test_base64_lines() {
+        let mut encoder = Base64Encoder::new(FixedString::<256>::new());
+        encoder.write(&[0; 120]).unwrap();
+
+        let encoded = encoder.finish().unwrap();
+        let lines = encoded.as_str().lines().map(str::len);
+
+        assert!(lines.eq([76, 76, 8].iter().copied()));
+    }
+
+    #[kernel_test]
+    fn test_dump_round_trip() {
+        let mut buffer = Buffer::new();
+        let mut dump = Dump::new(|bytes: &[u8]| buffer.push(bytes));
+
+        dump.record(RecordKind::Reason, &[b"page fault"]);
+        dump.record(RecordKind::Memory, &[&0x1000u64.to_le_bytes(), b"\xcc"]);
+        dump.finish();
+
+        let records = parse(buffer.as_slice()).unwrap();
+        let kinds = records.map(|record| (record.kind, record.payload.len()));
+
+        assert!(kinds.eq([(1, 10), (8, 9)].iter().copied()));
+
+        let length = buffer.length;
+        buffer.bytes[20] ^= 1;
+        assert!(matches!(
+            parse(buffer.as_slice()),
+            Err(FormatError::ChecksumMismatch { .. })
+        ));
+        assert_eq!(
+            parse(&buffer.as_slice()[..length - 3]).err(),
+            Some(FormatError::Truncated)
+        );
+        assert_eq!(parse(b"FERROSCX").err(), Some(FormatError::BadMagic));
+    }
+
+    #[kernel_test]
+    fn test_encode_registers() {
+        let frame = TrapFrame {
+            rax: 1,
+            rip: 0x20_1000,
+            error_code: 2,
+            ..TrapFrame::default()
+        };
+
+        let bytes = encode_registers(&frame, [0, 0xdead, 0, 0]);
+        let register = |name| {
+            let index = REGISTER_NAMES.iter().position(|n| *n == name).unwrap();
+            read_u64(&bytes[index * 8..]).unwrap()
+        };
+
+        assert_eq!(register("rax"), 1);
+        assert_eq!(register("rip"), 0x20_1000);
+        assert_eq!(register("error"), 2);
+        assert_eq!(register("cr2"), 0xdead);
+    }
+
+    #[kernel_test]
+    fn test_region_limits() {
+        assert_eq!(
+            add_region("huge", 0, MAX_REGION_SIZE + 1),
+            Err(CrashDumpError::RegionTooLarge(MAX_REGION_SIZE + 1))
+        );
+    }
+
+    #[cfg(target_os = "none")]
+    #[test_case]
+    fn test_collect() {
+        static BUFFER: spin::Mutex<[u8; 128 * 1024]> = spin::Mutex::new([0; 128 * 1024]);
+
+        let mut buffer = BUFFER.lock();
+        let mut length = 0;
+        let watched = [0x5au8; 16];
+        let regions = [Some(Region {
+            name: "watched",
+            address: watched.as_ptr() as u64,
+            length: watched.len(),
+        })];
+
+        let frame = TrapFrame {
+            rip: readable as *const () as u64,
+            rsp: watched.as_ptr() as u64,
+            ..TrapFrame::default()
+        };
+
+        collect(
+            |bytes: &[u8]| {
+                buffer[length..length + bytes.len()].copy_from_slice(bytes);
+                length += bytes.len();
+            },
+            "test",
+            &frame,
+            &regions,
+        );
+
+        let records = parse(&buffer[..length]).unwrap();
+        let kinds = records.clone().map(|record| record.kind);
+
+        assert!(kinds.eq([1, 2, 3, 4, 5, 6, 7, 8].iter().copied()));
+
+        let memory = records.last().unwrap().payload;
+        assert_eq!(&memory[10..17], b"watched");
+        assert_eq!(&memory[17..], &watched);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::nucleus::debug::memory;
+
+pub const MAX_BREAKPOINTS: usize = 32;
+
+const INT3: u8 = 0xcc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakpointError {
+    Full,
+    Unmapped,
+}
+
+#[derive(Clone, Copy)]
+struct Breakpoint {
+    address: u64,
+    original: u8,
+}
+
+/// Software breakpoints: each replaces the first byte of an instruction with `int3`, keeping the
+/// original byte to put back.
+pub struct Breakpoints {
+    slots: [Option<Breakpoint>; MAX_BREAKPOINTS],
+}
+
+impl Breakpoints {
+    pub const fn new() -> Self {
+        Self {
+            slots: [None; MAX_BREAKPOINTS],
+        }
+    }
+
+    pub fn contains(&self, address: u64) -> bool {
+        self.slots
+            .iter()
+            .flatten()
+            .any(|breakpoint| breakpoint.address == address)
+    }
+
+    pub fn insert(&mut self, address: u64) -> Result<(), BreakpointError> {
+        if self.contains(address) {
+            return Ok(());
+        }
+
+        if !memory::is_mapped(address, 1) {
+            return Err(BreakpointError::Unmapped);
+        }
+
+        let slot = self
+            .slots
+            .iter_mut()
+            .find(|slot| slot.is_none())
+            .ok_or(BreakpointError::Full)?;
+
+        let mut original = [0];
+
+        unsafe {
+            memory::read(address, &mut original);
+            memory::write(address, &[INT3]);
+        }
+
+        *slot = Some(Breakpoint {
+            address,
+            original: original[0],
+        });
+        Ok(())
+    }
+
+    /// Restores the original byte. Removing an unknown address is not an error, matching GDB.
+    pub fn remove(&mut self, address: u64) {
+        for slot in self.slots.iter_mut() {
+            if let Some(breakpoint) = slot.filter(|breakpoint| breakpoint.address == address) {
+                unsafe { memory::write(breakpoint.address, &[breakpoint.original]) };
+                *slot = None;
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for slot in self.slots.iter_mut() {
+            if let Some(breakpoint) = slot.take() {
+                unsafe { memory::write(breakpoint.address, &[breakpoint.original]) };
+            }
+        }
+    }
+}
+
+impl Default for Breakpoints {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(test, target_os = "none"))]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_insert_and_remove() {
+        let mut code = [0x90u8; 4];
+        let address = code.as_mut_ptr() as u64 + 1;
+        let mut breakpoints = Breakpoints::new();
+
+        assert_eq!(breakpoints.insert(address), Ok(()));
+        assert!(breakpoints.contains(address));
+        assert_eq!(core::hint::black_box(code), [0x90, INT3, 0x90, 0x90]);
+
+        breakpoints.remove(address);
+        assert!(!breakpoints.contains(address));
+        assert_eq!(core::hint::black_box(code), [0x90; 4]);
+    }
+
+    #[test_case]
+    fn test_full() {
+        let mut code = [0x90u8; MAX_BREAKPOINTS + 1];
+        let base = code.as_mut_ptr() as u64;
+        let mut breakpoints = Breakpoints::new();
+
+        for offset in 0..MAX_BREAKPOINTS as u64 {
+            assert_eq!(breakpoints.insert(base + offset), Ok(()));
+        }
+
+        assert_eq!(
+            breakpoints.insert(base + MAX_BREAKPOINTS as u64),
+            Err(BreakpointError::Full)
+        );
+
+        breakpoints.clear();
+        assert_eq!(core::hint::black_box(code), [0x90; MAX_BREAKPOINTS + 1]);
+    }
+}
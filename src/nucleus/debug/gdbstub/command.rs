@@ -0,0 +1,206 @@
+use super::packet::parse_hex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakpointKind {
+    Software,
+    Hardware,
+    Write,
+    Read,
+    Access,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command<'a> {
+    HaltReason,
+    ReadRegisters,
+    WriteRegisters(&'a [u8]),
+    ReadRegister(usize),
+    WriteRegister(usize, &'a [u8]),
+    ReadMemory {
+        address: u64,
+        length: usize,
+    },
+    WriteMemory {
+        address: u64,
+        length: usize,
+        data: &'a [u8],
+    },
+    InsertBreakpoint {
+        kind: BreakpointKind,
+        address: u64,
+        length: usize,
+    },
+    RemoveBreakpoint {
+        kind: BreakpointKind,
+        address: u64,
+        length: usize,
+    },
+    Continue(Option<u64>),
+    Step(Option<u64>),
+    Detach,
+    Kill,
+    SetThread,
+    Query(&'a [u8]),
+    Malformed,
+    Unsupported,
+}
+
+impl<'a> Command<'a> {
+    pub fn parse(packet: &'a [u8]) -> Self {
+        let (&first, arguments) = match packet.split_first() {
+            Some(split) => split,
+            None => return Self::Unsupported,
+        };
+
+        let command = match first {
+            b'?' => Some(Self::HaltReason),
+            b'g' => Some(Self::ReadRegisters),
+            b'G' => Some(Self::WriteRegisters(arguments)),
+            b'p' => parse_hex(arguments).map(|number| Self::ReadRegister(number as usize)),
+            b'P' => split(arguments, b'=').and_then(|(number, value)| {
+                Some(Self::WriteRegister(parse_hex(number)? as usize, value))
+            }),
+            b'm' => {
+                parse_range(arguments).map(|(address, length)| Self::ReadMemory { address, length })
+            }
+            b'M' => split(arguments, b':').and_then(|(range, data)| {
+                let (address, length) = parse_range(range)?;
+
+                Some(Self::WriteMemory {
+                    address,
+                    length,
+                    data,
+                })
+            }),
+            b'Z' | b'z' => parse_breakpoint(arguments).map(|(kind, address, length)| match first {
+                b'Z' => Self::InsertBreakpoint {
+                    kind,
+                    address,
+                    length,
+                },
+                _ => Self::RemoveBreakpoint {
+                    kind,
+                    address,
+                    length,
+                },
+            }),
+            b'c' => parse_resume(arguments).map(Self::Continue),
+            b's' => parse_resume(arguments).map(Self::Step),
+            b'D' => Some(Self::Detach),
+            b'k' => Some(Self::Kill),
+            b'H' | b'T' => Some(Self::SetThread),
+            b'q' => Some(Self::Query(arguments)),
+            _ => return Self::Unsupported,
+        };
+
+        command.unwrap_or(Self::Malformed)
+    }
+}
+
+fn split(arguments: &[u8], separator: u8) -> Option<(&[u8], &[u8])> {
+    let index = arguments.iter().position(|byte| *byte == separator)?;
+    Some((&arguments[..index], &arguments[index + 1..]))
+}
+
+fn parse_range(arguments: &[u8]) -> Option<(u64, usize)> {
+    let (address, length) = split(arguments, b',')?;
+    Some((parse_hex(address)?, parse_hex(length)? as usize))
+}
+
+fn parse_breakpoint(arguments: &[u8]) -> Option<(BreakpointKind, u64, usize)> {
+    let (kind, range) = split(arguments, b',')?;
+    let (address, length) = parse_range(range)?;
+
+    let kind = match kind {
+        b"0" => BreakpointKind::Software,
+        b"1" => BreakpointKind::Hardware,
+        b"2" => BreakpointKind::Write,
+        b"3" => BreakpointKind::Read,
+        b"4" => BreakpointKind::Access,
+        _ => return None,
+    };
+
+    Some((kind, address, length))
+}
+
+fn parse_resume(arguments: &[u8]) -> Option<Option<u64>> {
+    match arguments {
+        [] => Some(None),
+        address => parse_hex(address).map(Some),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_parse_registers() {
+        assert_eq!(Command::parse(b"g"), Command::ReadRegisters);
+        assert_eq!(Command::parse(b"G0011"), Command::WriteRegisters(b"0011"));
+        assert_eq!(Command::parse(b"p10"), Command::ReadRegister(16));
+        assert_eq!(
+            Command::parse(b"P7=0080ffff"),
+            Command::WriteRegister(7, b"0080ffff")
+        );
+        assert_eq!(Command::parse(b"P7"), Command::Malformed);
+    }
+
+    #[kernel_test]
+    fn test_parse_memory() {
+        assert_eq!(
+            Command::parse(b"mffff800000001000,40"),
+            Command::ReadMemory {
+                address: 0xffff_8000_0000_1000,
+                length: 0x40,
+            }
+        );
+        assert_eq!(
+            Command::parse(b"M2000,2:cc90"),
+            Command::WriteMemory {
+                address: 0x2000,
+                length: 2,
+                data: b"cc90",
+            }
+        );
+        assert_eq!(Command::parse(b"m2000"), Command::Malformed);
+        assert_eq!(Command::parse(b"mxyz,1"), Command::Malformed);
+    }
+
+    #[kernel_test]
+    fn test_parse_breakpoints() {
+        assert_eq!(
+            Command::parse(b"Z0,201a3c,1"),
+            Command::InsertBreakpoint {
+                kind: BreakpointKind::Software,
+                address: 0x201a3c,
+                length: 1,
+            }
+        );
+        assert_eq!(
+            Command::parse(b"z2,3000,8"),
+            Command::RemoveBreakpoint {
+                kind: BreakpointKind::Write,
+                address: 0x3000,
+                length: 8,
+            }
+        );
+        assert_eq!(Command::parse(b"Z9,3000,8"), Command::Malformed);
+    }
+
+    #[kernel_test]
+    fn test_parse_other() {
+        assert_eq!(Command::parse(b"?"), Command::HaltReason);
+        assert_eq!(Command::parse(b"c"), Command::Continue(None));
+        assert_eq!(Command::parse(b"s201000"), Command::Step(Some(0x201000)));
+        assert_eq!(Command::parse(b"D"), Command::Detach);
+        assert_eq!(Command::parse(b"Hg0"), Command::SetThread);
+        assert_eq!(
+            Command::parse(b"qSupported:xmlRegisters=i386"),
+            Command::Query(b"Supported:xmlRegisters=i386")
+        );
+        assert_eq!(Command::parse(b"vCont?"), Command::Unsupported);
+        assert_eq!(Command::parse(b""), Command::Unsupported);
+    }
+}
@@ -0,0 +1,381 @@
+mod breakpoints;
+mod command;
+mod packet;
+mod registers;
+
+pub use breakpoints::{BreakpointError, Breakpoints, MAX_BREAKPOINTS};
+pub use command::{BreakpointKind, Command};
+pub use packet::{Connection, PACKET_SIZE};
+
+use super::memory;
+use super::trap::{TrapFrame, BREAKPOINT_VECTOR, DEBUG_VECTOR, TRAP_FLAG};
+use crate::driver::serial::{self, ComPort, Config, SerialError};
+use crate::libs::format::FixedString;
+use crate::nucleus::sync::IrqMutex;
+use core::fmt::Write;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+pub const SIGINT: u8 = 2;
+pub const SIGTRAP: u8 = 5;
+pub const SIGABRT: u8 = 6;
+
+/// Largest `m` or `M` transfer, so its hex encoding fits in a packet.
+const MAX_TRANSFER: usize = PACKET_SIZE / 2 - 16;
+
+static STUB: IrqMutex<Option<Stub>> = IrqMutex::new(None);
+static PENDING_SIGNAL: AtomicU8 = AtomicU8::new(0);
+
+struct SerialConnection(ComPort);
+
+impl Connection for SerialConnection {
+    fn read_byte(&mut self) -> u8 {
+        loop {
+            if let Some(byte) = serial::read_byte(self.0) {
+                return byte;
+            }
+
+            core::hint::spin_loop();
+        }
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        serial::write_bytes(self.0, bytes).ok();
+    }
+}
+
+enum Action {
+    Reply,
+    Continue,
+    Step,
+    Detach,
+}
+
+struct Stub {
+    port: ComPort,
+    attached: bool,
+    breakpoints: Breakpoints,
+    input: [u8; PACKET_SIZE],
+    output: FixedString<PACKET_SIZE>,
+}
+
+impl Stub {
+    const fn new(port: ComPort) -> Self {
+        Self {
+            port,
+            attached: false,
+            breakpoints: Breakpoints::new(),
+            input: [0; PACKET_SIZE],
+            output: FixedString::new(),
+        }
+    }
+
+    /// Serves GDB until it resumes the kernel. A stop is only reported to a GDB that is already
+    /// attached; a new one asks with `?`.
+    fn enter(&mut self, frame: &mut TrapFrame) {
+        let mut connection = SerialConnection(self.port);
+
+        let signal = match PENDING_SIGNAL.swap(0, Ordering::SeqCst) {
+            0 => SIGTRAP,
+            signal => signal,
+        };
+
+        frame.rflags &= !TRAP_FLAG;
+
+        if frame.vector == BREAKPOINT_VECTOR && self.breakpoints.contains(frame.rip - 1) {
+            frame.rip -= 1;
+        }
+
+        if self.attached {
+            self.output.clear();
+            write!(self.output, "S{:02x}", signal).ok();
+            packet::write_packet(&mut connection, self.output.as_bytes());
+        }
+
+        loop {
+            let length = packet::read_packet(&mut connection, &mut self.input);
+            self.output.clear();
+
+            let action = execute(
+                Command::parse(&self.input[..length]),
+                frame,
+                signal,
+                &mut self.breakpoints,
+                &mut self.output,
+            );
+
+            match action {
+                Action::Reply => {
+                    self.attached = true;
+                    packet::write_packet(&mut connection, self.output.as_bytes());
+                }
+                Action::Continue => return,
+                Action::Step => {
+                    frame.rflags |= TRAP_FLAG;
+                    return;
+                }
+                Action::Detach => {
+                    if !self.output.is_empty() {
+                        packet::write_packet(&mut connection, self.output.as_bytes());
+                    }
+
+                    self.attached = false;
+                    return;
+                }
+            }
+        }
+    }
+}
+
+fn execute(
+    command: Command,
+    frame: &mut TrapFrame,
+    signal: u8,
+    breakpoints: &mut Breakpoints,
+    output: &mut FixedString<PACKET_SIZE>,
+) -> Action {
+    match command {
+        Command::HaltReason => {
+            write!(output, "S{:02x}", signal).ok();
+        }
+        Command::ReadRegisters => {
+            registers::encode_all(output, frame).ok();
+        }
+        Command::WriteRegisters(hex) => reply(output, registers::decode_all(frame, hex)),
+        Command::ReadRegister(number) => {
+            if registers::encode(output, frame, number).is_none() {
+                output.write_str("E00").ok();
+            }
+        }
+        Command::WriteRegister(number, hex) => {
+            let written = registers::decode(number, hex)
+                .is_some_and(|value| registers::write(frame, number, value));
+            reply(output, written);
+        }
+        Command::ReadMemory { address, length } => {
+            let length = core::cmp::min(length, MAX_TRANSFER);
+
+            if !memory::is_mapped(address, length) {
+                output.write_str("E14").ok();
+                return Action::Reply;
+            }
+
+            let mut bytes = [0; 256];
+
+            for offset in (0..length).step_by(bytes.len()) {
+                let chunk = &mut bytes[..core::cmp::min(256, length - offset)];
+                unsafe { memory::read(address + offset as u64, chunk) };
+                packet::write_hex(output, chunk).ok();
+            }
+        }
+        Command::WriteMemory {
+            address,
+            length,
+            data,
+        } => {
+            let mut bytes = [0; MAX_TRANSFER];
+
+            match packet::decode_hex(data, &mut bytes) {
+                Some(decoded) if decoded == length && memory::is_mapped(address, length) => {
+                    unsafe { memory::write(address, &bytes[..length]) };
+                    output.write_str("OK").ok();
+                }
+                _ => {
+                    output.write_str("E14").ok();
+                }
+            }
+        }
+        Command::InsertBreakpoint {
+            kind: BreakpointKind::Software,
+            address,
+            ..
+        } => match breakpoints.insert(address) {
+            Ok(()) => {
+                output.write_str("OK").ok();
+            }
+            Err(BreakpointError::Full) => {
+                output.write_str("E1c").ok();
+            }
+            Err(BreakpointError::Unmapped) => {
+                output.write_str("E14").ok();
+            }
+        },
+        Command::RemoveBreakpoint {
+            kind: BreakpointKind::Software,
+            address,
+            ..
+        } => {
+            breakpoints.remove(address);
+            output.write_str("OK").ok();
+        }
+        Command::Continue(address) => {
+            frame.rip = address.unwrap_or(frame.rip);
+            return Action::Continue;
+        }
+        Command::Step(address) => {
+            frame.rip = address.unwrap_or(frame.rip);
+            return Action::Step;
+        }
+        Command::Detach => {
+            breakpoints.clear();
+            output.write_str("OK").ok();
+            return Action::Detach;
+        }
+        Command::Kill => {
+            breakpoints.clear();
+            return Action::Detach;
+        }
+        Command::SetThread => {
+            output.write_str("OK").ok();
+        }
+        Command::Query(query) => query_reply(output, query),
+        Command::Malformed => {
+            output.write_str("E01").ok();
+        }
+        Command::InsertBreakpoint { .. }
+        | Command::RemoveBreakpoint { .. }
+        | Command::Unsupported => {}
+    }
+
+    Action::Reply
+}
+
+fn reply(output: &mut FixedString<PACKET_SIZE>, success: bool) {
+    let reply = match success {
+        true => "OK",
+        false => "E01",
+    };
+
+    output.write_str(reply).ok();
+}
+
+fn query_reply(output: &mut FixedString<PACKET_SIZE>, query: &[u8]) {
+    if query.starts_with(b"Supported") {
+        write!(output, "PacketSize={:x}", PACKET_SIZE).ok();
+        return;
+    }
+
+    let reply = match query {
+        b"Attached" => "1",
+        b"C" => "QC1",
+        b"fThreadInfo" => "m1",
+        b"sThreadInfo" => "l",
+        _ => "",
+    };
+
+    output.write_str(reply).ok();
+}
+
+/// Serves GDB on `port` from the next breakpoint or debug trap on. Memory requests are checked
+/// against the page tables through the bootloader's physical memory mapping.
+pub fn init(port: ComPort, physical_memory_offset: u64) -> Result<(), SerialError> {
+    serial::init(port, Config::new())?;
+    memory::set_physical_memory_offset(physical_memory_offset);
+
+    *STUB.lock() = Some(Stub::new(port));
+    Ok(())
+}
+
+/// Also `false` while the stub itself is running, so a panic inside it does not trap again.
+pub fn is_active() -> bool {
+    STUB.try_lock().is_some_and(|stub| stub.is_some())
+}
+
+/// Stops in the stub, for example to wait for GDB to attach.
+pub fn breakpoint() {
+    if is_active() {
+        x86_64::instructions::interrupts::int3();
+    }
+}
+
+/// Reports a panic to GDB as `SIGABRT` and waits for it, so the panicking state can be inspected.
+pub fn halt_on_panic() {
+    if is_active() {
+        PENDING_SIGNAL.store(SIGABRT, Ordering::SeqCst);
+        x86_64::instructions::interrupts::int3();
+    }
+}
+
+/// Runs the stub for a breakpoint or debug trap. Returns `false` when no stub is active, or when
+/// the trap came from the stub itself.
+pub(super) fn handle_trap(frame: &mut TrapFrame) -> bool {
+    debug_assert!(frame.vector == BREAKPOINT_VECTOR || frame.vector == DEBUG_VECTOR);
+
+    let mut stub = match STUB.try_lock() {
+        Some(stub) => stub,
+        None => return false,
+    };
+
+    match stub.as_mut() {
+        Some(stub) => {
+            stub.enter(frame);
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    fn run(packet: &[u8], frame: &mut TrapFrame) -> FixedString<PACKET_SIZE> {
+        let mut output = FixedString::new();
+        let mut breakpoints = Breakpoints::new();
+
+        execute(
+            Command::parse(packet),
+            frame,
+            SIGTRAP,
+            &mut breakpoints,
+            &mut output,
+        );
+        output
+    }
+
+    #[kernel_test]
+    fn test_execute_queries() {
+        let mut frame = TrapFrame::default();
+
+        assert_eq!(run(b"?", &mut frame).as_str(), "S05");
+        assert_eq!(
+            run(b"qSupported:multiprocess+", &mut frame).as_str(),
+            "PacketSize=1000"
+        );
+        assert_eq!(run(b"qAttached", &mut frame).as_str(), "1");
+        assert_eq!(run(b"qTStatus", &mut frame).as_str(), "");
+        assert_eq!(run(b"vMustReplyEmpty", &mut frame).as_str(), "");
+        assert_eq!(run(b"p", &mut frame).as_str(), "E01");
+        assert_eq!(run(b"p40", &mut frame).as_str(), "E00");
+    }
+
+    #[kernel_test]
+    fn test_execute_registers() {
+        let mut frame = TrapFrame::default();
+
+        assert_eq!(run(b"P10=0010200000000000", &mut frame).as_str(), "OK");
+        assert_eq!(frame.rip, 0x20_1000);
+        assert_eq!(run(b"p10", &mut frame).as_str(), "0010200000000000");
+        assert_eq!(run(b"P11=00", &mut frame).as_str(), "E01");
+    }
+
+    #[kernel_test]
+    fn test_execute_resume() {
+        let mut frame = TrapFrame {
+            rip: 0x1000,
+            ..TrapFrame::default()
+        };
+
+        assert!(matches!(
+            execute(
+                Command::parse(b"s2000"),
+                &mut frame,
+                SIGTRAP,
+                &mut Breakpoints::new(),
+                &mut FixedString::new(),
+            ),
+            Action::Step
+        ));
+        assert_eq!(frame.rip, 0x2000);
+    }
+}
@@ -0,0 +1,217 @@
+/// Largest packet payload, advertised to GDB through `qSupported`.
+pub const PACKET_SIZE: usize = 4096;
+
+pub trait Connection {
+    /// Blocks until a byte arrives.
+    fn read_byte(&mut self) -> u8;
+    fn write_bytes(&mut self, bytes: &[u8]);
+}
+
+/// Waits for a `$payload#checksum` packet with a valid checksum, acknowledges it and returns the
+/// payload length. Bytes between packets, such as stray acknowledgements, are skipped; corrupt or
+/// oversized packets are rejected with `-` so GDB resends them.
+pub fn read_packet(connection: &mut impl Connection, buffer: &mut [u8]) -> usize {
+    loop {
+        while connection.read_byte() != b'$' {}
+
+        let mut length = 0;
+        let mut checksum = 0u8;
+        let mut overflowed = false;
+
+        loop {
+            let byte = connection.read_byte();
+
+            match byte {
+                b'#' => break,
+                b'$' => {
+                    length = 0;
+                    checksum = 0;
+                    overflowed = false;
+                }
+                _ if length < buffer.len() => {
+                    buffer[length] = byte;
+                    length += 1;
+                    checksum = checksum.wrapping_add(byte);
+                }
+                _ => overflowed = true,
+            }
+        }
+
+        let high = hex_value(connection.read_byte());
+        let low = hex_value(connection.read_byte());
+
+        match (high, low) {
+            (Some(high), Some(low)) if high << 4 | low == checksum && !overflowed => {
+                connection.write_bytes(b"+");
+                return length;
+            }
+            _ => connection.write_bytes(b"-"),
+        }
+    }
+}
+
+/// Sends `payload` until GDB acknowledges it with `+`.
+pub fn write_packet(connection: &mut impl Connection, payload: &[u8]) {
+    let checksum = payload
+        .iter()
+        .fold(0u8, |checksum, byte| checksum.wrapping_add(*byte));
+    let trailer = [
+        b'#',
+        HEX_DIGITS[(checksum >> 4) as usize],
+        HEX_DIGITS[(checksum & 0xf) as usize],
+    ];
+
+    loop {
+        connection.write_bytes(b"$");
+        connection.write_bytes(payload);
+        connection.write_bytes(&trailer);
+
+        loop {
+            match connection.read_byte() {
+                b'+' => return,
+                b'-' => break,
+                _ => {}
+            }
+        }
+    }
+}
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+pub fn hex_value(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Parses a big-endian hexadecimal number, as used for addresses and lengths.
+pub fn parse_hex(digits: &[u8]) -> Option<u64> {
+    if digits.is_empty() || digits.len() > 16 {
+        return None;
+    }
+
+    digits.iter().try_fold(0u64, |value, digit| {
+        Some(value << 4 | hex_value(*digit)? as u64)
+    })
+}
+
+/// Decodes hex pairs into `output`, returning the number of bytes written.
+pub fn decode_hex(digits: &[u8], output: &mut [u8]) -> Option<usize> {
+    if !digits.len().is_multiple_of(2) || digits.len() / 2 > output.len() {
+        return None;
+    }
+
+    for (pair, byte) in digits.chunks(2).zip(output.iter_mut()) {
+        *byte = hex_value(pair[0])? << 4 | hex_value(pair[1])?;
+    }
+
+    Some(digits.len() / 2)
+}
+
+pub fn write_hex(output: &mut impl core::fmt::Write, bytes: &[u8]) -> core::fmt::Result {
+    bytes
+        .iter()
+        .try_for_each(|byte| write!(output, "{:02x}", byte))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::buffer::RingBuffer;
+    use crate::libs::format::FixedString;
+    use crate::libs::testing::kernel_test;
+
+    struct Loopback {
+        input: RingBuffer<u8, 128>,
+        output: FixedString<128>,
+    }
+
+    impl Loopback {
+        fn new(input: &[u8]) -> Self {
+            let mut loopback = Self {
+                input: RingBuffer::new(),
+                output: FixedString::new(),
+            };
+
+            input
+                .iter()
+                .for_each(|byte| loopback.input.push(*byte).unwrap());
+            loopback
+        }
+    }
+
+    impl Connection for Loopback {
+        fn read_byte(&mut self) -> u8 {
+            self.input.pop().expect("read past the end of the input")
+        }
+
+        fn write_bytes(&mut self, bytes: &[u8]) {
+            core::fmt::Write::write_str(&mut self.output, core::str::from_utf8(bytes).unwrap())
+                .unwrap();
+        }
+    }
+
+    #[kernel_test]
+    fn test_read_packet() {
+        let mut connection = Loopback::new(b"+$m1000,4#8e");
+        let mut buffer = [0; 16];
+
+        let length = read_packet(&mut connection, &mut buffer);
+
+        assert_eq!(&buffer[..length], b"m1000,4");
+        assert_eq!(connection.output.as_str(), "+");
+    }
+
+    #[kernel_test]
+    fn test_read_packet_rejects_bad_checksum() {
+        let mut connection = Loopback::new(b"$g#00$g#67");
+        let mut buffer = [0; 16];
+
+        let length = read_packet(&mut connection, &mut buffer);
+
+        assert_eq!(&buffer[..length], b"g");
+        assert_eq!(connection.output.as_str(), "-+");
+    }
+
+    #[kernel_test]
+    fn test_read_packet_rejects_oversized() {
+        let mut connection = Loopback::new(b"$qSupported#37$?#3f");
+        let mut buffer = [0; 4];
+
+        let length = read_packet(&mut connection, &mut buffer);
+
+        assert_eq!(&buffer[..length], b"?");
+        assert_eq!(connection.output.as_str(), "-+");
+    }
+
+    #[kernel_test]
+    fn test_write_packet() {
+        let mut connection = Loopback::new(b"-+");
+
+        write_packet(&mut connection, b"OK");
+
+        assert_eq!(connection.output.as_str(), "$OK#9a$OK#9a");
+    }
+
+    #[kernel_test]
+    fn test_hex() {
+        assert_eq!(parse_hex(b"ffff8000001a2b3c"), Some(0xffff_8000_001a_2b3c));
+        assert_eq!(parse_hex(b"1A"), Some(0x1a));
+        assert_eq!(parse_hex(b""), None);
+        assert_eq!(parse_hex(b"12g"), None);
+        assert_eq!(parse_hex(b"10000000000000000"), None);
+
+        let mut bytes = [0; 4];
+        assert_eq!(decode_hex(b"deadbeef", &mut bytes), Some(4));
+        assert_eq!(bytes, [0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(decode_hex(b"abc", &mut bytes), None);
+        assert_eq!(decode_hex(b"0011223344", &mut bytes), None);
+
+        let mut text = FixedString::<16>::new();
+        write_hex(&mut text, &[0x00, 0x7f, 0xff]).unwrap();
+        assert_eq!(text.as_str(), "007fff");
+    }
+}
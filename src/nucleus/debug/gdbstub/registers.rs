@@ -0,0 +1,180 @@
+use super::packet::{decode_hex, write_hex};
+use crate::nucleus::debug::TrapFrame;
+use x86_64::registers::segmentation::{Segment, DS, ES, FS, GS};
+
+/// Registers in GDB's amd64 order: 16 general purpose registers, rip, eflags and the six segment
+/// selectors. The x87 and SSE registers that follow are left unavailable.
+pub const REGISTER_COUNT: usize = 24;
+
+const RIP: usize = 16;
+const EFLAGS: usize = 17;
+
+pub const fn size(number: usize) -> usize {
+    match number {
+        0..=RIP => 8,
+        _ => 4,
+    }
+}
+
+pub fn read(frame: &TrapFrame, number: usize) -> Option<u64> {
+    let value = match number {
+        0 => frame.rax,
+        1 => frame.rbx,
+        2 => frame.rcx,
+        3 => frame.rdx,
+        4 => frame.rsi,
+        5 => frame.rdi,
+        6 => frame.rbp,
+        7 => frame.rsp,
+        8 => frame.r8,
+        9 => frame.r9,
+        10 => frame.r10,
+        11 => frame.r11,
+        12 => frame.r12,
+        13 => frame.r13,
+        14 => frame.r14,
+        15 => frame.r15,
+        RIP => frame.rip,
+        EFLAGS => frame.rflags,
+        18 => frame.cs,
+        19 => frame.ss,
+        20 => DS::get_reg().0 as u64,
+        21 => ES::get_reg().0 as u64,
+        22 => FS::get_reg().0 as u64,
+        23 => GS::get_reg().0 as u64,
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+/// Updates a register. Segment selectors are accepted but left unchanged, since reloading them
+/// under the kernel is never what a debugger session wants.
+pub fn write(frame: &mut TrapFrame, number: usize, value: u64) -> bool {
+    let register = match number {
+        0 => &mut frame.rax,
+        1 => &mut frame.rbx,
+        2 => &mut frame.rcx,
+        3 => &mut frame.rdx,
+        4 => &mut frame.rsi,
+        5 => &mut frame.rdi,
+        6 => &mut frame.rbp,
+        7 => &mut frame.rsp,
+        8 => &mut frame.r8,
+        9 => &mut frame.r9,
+        10 => &mut frame.r10,
+        11 => &mut frame.r11,
+        12 => &mut frame.r12,
+        13 => &mut frame.r13,
+        14 => &mut frame.r14,
+        15 => &mut frame.r15,
+        RIP => &mut frame.rip,
+        EFLAGS => &mut frame.rflags,
+        18..=23 => return true,
+        _ => return false,
+    };
+
+    *register = value;
+    true
+}
+
+/// Writes one register as little-endian hex.
+pub fn encode(
+    output: &mut impl core::fmt::Write,
+    frame: &TrapFrame,
+    number: usize,
+) -> Option<core::fmt::Result> {
+    let value = read(frame, number)?;
+    Some(write_hex(output, &value.to_le_bytes()[..size(number)]))
+}
+
+pub fn encode_all(output: &mut impl core::fmt::Write, frame: &TrapFrame) -> core::fmt::Result {
+    (0..REGISTER_COUNT).try_for_each(|number| encode(output, frame, number).unwrap_or(Ok(())))
+}
+
+/// Parses one little-endian hex register value.
+pub fn decode(number: usize, hex: &[u8]) -> Option<u64> {
+    let mut bytes = [0; 8];
+
+    match decode_hex(hex, &mut bytes)? {
+        length if length == size(number) => Some(u64::from_le_bytes(bytes)),
+        _ => None,
+    }
+}
+
+/// Applies a `G` packet, or nothing if it is malformed. GDB may send fewer registers than it
+/// received.
+pub fn decode_all(frame: &mut TrapFrame, hex: &[u8]) -> bool {
+    let mut updated = *frame;
+    let mut hex = hex;
+    let mut number = 0;
+
+    while !hex.is_empty() && number < REGISTER_COUNT {
+        let digits = size(number) * 2;
+
+        if hex.len() < digits {
+            return false;
+        }
+
+        match decode(number, &hex[..digits]) {
+            Some(value) => write(&mut updated, number, value),
+            None => return false,
+        };
+
+        hex = &hex[digits..];
+        number += 1;
+    }
+
+    *frame = updated;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::format::FixedString;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_encode() {
+        let frame = TrapFrame {
+            rax: 0x1122_3344_5566_7788,
+            rip: 0x20_1000,
+            rflags: 0x246,
+            ..TrapFrame::default()
+        };
+
+        let mut text = FixedString::<16>::new();
+        encode(&mut text, &frame, 0).unwrap().unwrap();
+        assert_eq!(text.as_str(), "8877665544332211");
+
+        text.clear();
+        encode(&mut text, &frame, EFLAGS).unwrap().unwrap();
+        assert_eq!(text.as_str(), "46020000");
+
+        assert!(encode(&mut text, &frame, REGISTER_COUNT).is_none());
+
+        let mut all = FixedString::<512>::new();
+        encode_all(&mut all, &frame).unwrap();
+        assert_eq!(all.len(), (17 * 8 + 7 * 4) * 2);
+        assert!(all.as_str()[RIP * 16..].starts_with("0010200000000000"));
+    }
+
+    #[kernel_test]
+    fn test_decode() {
+        let mut frame = TrapFrame::default();
+
+        assert_eq!(decode(RIP, b"0010200000000000"), Some(0x20_1000));
+        assert_eq!(decode(EFLAGS, b"0001"), None);
+
+        assert!(decode_all(&mut frame, b"01000000000000000200000000000000"));
+        assert_eq!((frame.rax, frame.rbx, frame.rcx), (1, 2, 0));
+        assert!(!decode_all(&mut frame, b"0300"));
+        assert!(!decode_all(&mut frame, b"0900000000000000xx"));
+        assert_eq!(frame.rax, 1);
+
+        assert!(write(&mut frame, 7, 0x8000));
+        assert_eq!(read(&frame, 7), Some(0x8000));
+        assert!(!write(&mut frame, REGISTER_COUNT, 0));
+    }
+}
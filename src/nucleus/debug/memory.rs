@@ -0,0 +1,108 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+use x86_64::registers::control::{Cr0, Cr0Flags, Cr3};
+use x86_64::structures::paging::page_table::PageTableEntry;
+use x86_64::structures::paging::{OffsetPageTable, PageTable, PageTableFlags, Translate};
+use x86_64::VirtAddr;
+
+const PAGE_SIZE: u64 = 4096;
+const UNKNOWN: u64 = u64::MAX;
+
+static PHYSICAL_MEMORY_OFFSET: AtomicU64 = AtomicU64::new(UNKNOWN);
+
+pub(super) fn set_physical_memory_offset(offset: u64) {
+    PHYSICAL_MEMORY_OFFSET.store(offset, Ordering::SeqCst);
+}
+
+/// Whether every page in the range is mapped, so a debugger probing a bad address gets an error instead
+/// of a page fault. Without the physical memory offset the page tables cannot be walked, and
+/// every canonical address is trusted.
+pub(super) fn is_mapped(address: u64, length: usize) -> bool {
+    let end = match address.checked_add(length as u64) {
+        Some(end) => end,
+        None => return false,
+    };
+
+    let offset = PHYSICAL_MEMORY_OFFSET.load(Ordering::SeqCst);
+    let first_page = address & !(PAGE_SIZE - 1);
+
+    (first_page..end)
+        .step_by(PAGE_SIZE as usize)
+        .all(|page| match VirtAddr::try_new(page) {
+            Ok(page) if offset != UNKNOWN => translate(offset, page),
+            Ok(_) => true,
+            Err(_) => false,
+        })
+}
+
+/// Visits the entries mapping `address` from the level 4 table down, stopping after a missing
+/// entry or a huge page. Returns `false` when the tables cannot be reached.
+pub(super) fn walk<F>(address: VirtAddr, mut visit: F) -> bool
+where
+    F: FnMut(u8, u16, &PageTableEntry),
+{
+    let offset = PHYSICAL_MEMORY_OFFSET.load(Ordering::SeqCst);
+
+    if offset == UNKNOWN {
+        return false;
+    }
+
+    let (frame, _) = Cr3::read();
+    let mut table = frame.start_address().as_u64();
+
+    let indices = [
+        address.p4_index(),
+        address.p3_index(),
+        address.p2_index(),
+        address.p1_index(),
+    ];
+
+    for (level, index) in (1..=4).rev().zip(indices.iter()) {
+        let entries = unsafe { &*((offset + table) as *const PageTable) };
+        let entry = &entries[*index];
+        visit(level, u16::from(*index), entry);
+
+        let flags = entry.flags();
+
+        if !flags.contains(PageTableFlags::PRESENT) || flags.contains(PageTableFlags::HUGE_PAGE) {
+            break;
+        }
+
+        table = entry.addr().as_u64();
+    }
+
+    true
+}
+
+fn translate(offset: u64, address: VirtAddr) -> bool {
+    let (frame, _) = Cr3::read();
+    let table = (offset + frame.start_address().as_u64()) as *mut PageTable;
+    let mapper = unsafe { OffsetPageTable::new(&mut *table, VirtAddr::new(offset)) };
+
+    mapper.translate_addr(address).is_some()
+}
+
+/// # Safety
+///
+/// The range must be mapped.
+pub(super) unsafe fn read(address: u64, buffer: &mut [u8]) {
+    for (index, byte) in buffer.iter_mut().enumerate() {
+        *byte = core::ptr::read_volatile((address as *const u8).add(index));
+    }
+}
+
+/// Writes through read-only mappings too, by clearing CR0.WP for the duration, so breakpoints can
+/// patch kernel code.
+///
+/// # Safety
+///
+/// The range must be mapped and nothing may depend on its old contents.
+pub(super) unsafe fn write(address: u64, bytes: &[u8]) {
+    let flags = Cr0::read();
+    Cr0::write(flags - Cr0Flags::WRITE_PROTECT);
+
+    for (index, byte) in bytes.iter().enumerate() {
+        core::ptr::write_volatile((address as *mut u8).add(index), *byte);
+    }
+
+    Cr0::write(flags);
+}
@@ -0,0 +1,14 @@
+pub mod crashdump;
+pub mod gdbstub;
+pub mod monitor;
+pub mod watchpoint;
+
+mod memory;
+mod trap;
+
+pub use trap::{trap_on_return, TrapFrame, TRAP_FLAG};
+
+pub(crate) use trap::{
+    breakpoint_entry, debug_entry, divide_error_entry, double_fault_entry,
+    general_protection_entry, invalid_opcode_entry, page_fault_entry,
+};
@@ -0,0 +1,217 @@
+use core::convert::TryFrom;
+
+/// Most bytes a single `w` command writes.
+pub const MAX_WRITE: usize = 16;
+
+const EXAMINE_LENGTH: usize = 0x40;
+const CODE_LENGTH: usize = 0x20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Registers,
+    Examine {
+        address: u64,
+        length: usize,
+    },
+    Modify {
+        address: u64,
+        bytes: [u8; MAX_WRITE],
+        length: usize,
+    },
+    Code {
+        address: Option<u64>,
+        length: usize,
+    },
+    PageWalk(u64),
+    InterruptTable,
+    Backtrace,
+    /// The kernel message ring, or the one the previous boot left behind.
+    Dmesg {
+        previous: bool,
+    },
+    Step,
+    Continue,
+    Help,
+    Empty,
+    Malformed,
+    Unknown,
+}
+
+impl Command {
+    /// Parses one line. Every number is hexadecimal, with or without `0x`.
+    pub fn parse(line: &str) -> Self {
+        let mut words = line.split_whitespace();
+
+        let name = match words.next() {
+            Some(name) => name,
+            None => return Self::Empty,
+        };
+
+        let command = match name {
+            "r" | "regs" => Some(Self::Registers),
+            "x" => parse_range(&mut words, EXAMINE_LENGTH)
+                .map(|(address, length)| Self::Examine { address, length }),
+            "w" => parse_write(&mut words),
+            "d" | "dis" => parse_code(&mut words),
+            "pt" => words.next().and_then(parse_number).map(Self::PageWalk),
+            "idt" => Some(Self::InterruptTable),
+            "bt" => Some(Self::Backtrace),
+            "dmesg" => parse_dmesg(&mut words),
+            "s" | "step" => Some(Self::Step),
+            "c" | "continue" => Some(Self::Continue),
+            "h" | "help" | "?" => Some(Self::Help),
+            _ => return Self::Unknown,
+        };
+
+        match (command, words.next()) {
+            (Some(command), None) => command,
+            _ => Self::Malformed,
+        }
+    }
+}
+
+fn parse_number(word: &str) -> Option<u64> {
+    let digits = word.strip_prefix("0x").unwrap_or(word);
+    u64::from_str_radix(digits, 16).ok()
+}
+
+fn parse_length(word: Option<&str>, default: usize) -> Option<usize> {
+    match word {
+        Some(word) => parse_number(word).map(|length| length as usize),
+        None => Some(default),
+    }
+}
+
+fn parse_range<'a, I>(words: &mut I, default_length: usize) -> Option<(u64, usize)>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let address = parse_number(words.next()?)?;
+    Some((address, parse_length(words.next(), default_length)?))
+}
+
+fn parse_write<'a, I>(words: &mut I) -> Option<Command>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let address = parse_number(words.next()?)?;
+    let mut bytes = [0; MAX_WRITE];
+    let mut length = 0;
+
+    for word in words {
+        let slot = bytes.get_mut(length)?;
+        *slot = u8::try_from(parse_number(word)?).ok()?;
+        length += 1;
+    }
+
+    match length {
+        0 => None,
+        _ => Some(Command::Modify {
+            address,
+            bytes,
+            length,
+        }),
+    }
+}
+
+fn parse_dmesg<'a, I>(words: &mut I) -> Option<Command>
+where
+    I: Iterator<Item = &'a str>,
+{
+    match words.next() {
+        None => Some(Command::Dmesg { previous: false }),
+        Some("prev") => Some(Command::Dmesg { previous: true }),
+        Some(_) => None,
+    }
+}
+
+fn parse_code<'a, I>(words: &mut I) -> Option<Command>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let address = match words.next() {
+        Some(address) => Some(parse_number(address)?),
+        None => None,
+    };
+
+    Some(Command::Code {
+        address,
+        length: parse_length(words.next(), CODE_LENGTH)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_parse_memory() {
+        assert_eq!(
+            Command::parse("x 0xb8000"),
+            Command::Examine {
+                address: 0xb8000,
+                length: EXAMINE_LENGTH,
+            }
+        );
+        assert_eq!(
+            Command::parse("  x ffff800000001000 10 "),
+            Command::Examine {
+                address: 0xffff_8000_0000_1000,
+                length: 0x10,
+            }
+        );
+
+        let mut bytes = [0; MAX_WRITE];
+        bytes[..2].copy_from_slice(&[0x90, 0xcc]);
+
+        assert_eq!(
+            Command::parse("w 2000 90 cc"),
+            Command::Modify {
+                address: 0x2000,
+                bytes,
+                length: 2,
+            }
+        );
+        assert_eq!(Command::parse("w 2000"), Command::Malformed);
+        assert_eq!(Command::parse("w 2000 100"), Command::Malformed);
+        assert_eq!(Command::parse("x"), Command::Malformed);
+        assert_eq!(Command::parse("x zz"), Command::Malformed);
+    }
+
+    #[kernel_test]
+    fn test_parse_code() {
+        assert_eq!(
+            Command::parse("d"),
+            Command::Code {
+                address: None,
+                length: CODE_LENGTH,
+            }
+        );
+        assert_eq!(
+            Command::parse("dis 201000 8"),
+            Command::Code {
+                address: Some(0x20_1000),
+                length: 8,
+            }
+        );
+    }
+
+    #[kernel_test]
+    fn test_parse_other() {
+        assert_eq!(Command::parse(""), Command::Empty);
+        assert_eq!(Command::parse("regs"), Command::Registers);
+        assert_eq!(Command::parse("pt 0x1000"), Command::PageWalk(0x1000));
+        assert_eq!(Command::parse("pt"), Command::Malformed);
+        assert_eq!(Command::parse("idt"), Command::InterruptTable);
+        assert_eq!(Command::parse("dmesg"), Command::Dmesg { previous: false });
+        assert_eq!(
+            Command::parse("dmesg prev"),
+            Command::Dmesg { previous: true }
+        );
+        assert_eq!(Command::parse("dmesg all"), Command::Malformed);
+        assert_eq!(Command::parse("c"), Command::Continue);
+        assert_eq!(Command::parse("c now"), Command::Malformed);
+        assert_eq!(Command::parse("quit"), Command::Unknown);
+    }
+}
@@ -0,0 +1,93 @@
+pub const LINE_SIZE: usize = 80;
+
+const BACKSPACE: u8 = 0x08;
+const DELETE: u8 = 0x7f;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edit {
+    Insert(u8),
+    Erase,
+    Submit,
+    Ignore,
+}
+
+/// Collects a line of printable ASCII, so it can always be read back as a `str`.
+pub struct LineEditor {
+    buffer: [u8; LINE_SIZE],
+    length: usize,
+}
+
+impl LineEditor {
+    pub const fn new() -> Self {
+        Self {
+            buffer: [0; LINE_SIZE],
+            length: 0,
+        }
+    }
+
+    /// Applies one input byte and reports what the terminal should echo.
+    pub fn feed(&mut self, byte: u8) -> Edit {
+        match byte {
+            b'\r' | b'\n' => Edit::Submit,
+            BACKSPACE | DELETE if self.length > 0 => {
+                self.length -= 1;
+                Edit::Erase
+            }
+            b' '..=b'~' if self.length < LINE_SIZE => {
+                self.buffer[self.length] = byte;
+                self.length += 1;
+                Edit::Insert(byte)
+            }
+            _ => Edit::Ignore,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.buffer[..self.length]).unwrap_or("")
+    }
+
+    pub fn clear(&mut self) {
+        self.length = 0;
+    }
+}
+
+impl Default for LineEditor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_edit_line() {
+        let mut line = LineEditor::new();
+
+        assert_eq!(line.feed(DELETE), Edit::Ignore);
+        assert_eq!(line.feed(b'x'), Edit::Insert(b'x'));
+        assert_eq!(line.feed(b'y'), Edit::Insert(b'y'));
+        assert_eq!(line.feed(BACKSPACE), Edit::Erase);
+        assert_eq!(line.feed(0x1b), Edit::Ignore);
+        assert_eq!(line.feed(b' '), Edit::Insert(b' '));
+        assert_eq!(line.feed(b'\r'), Edit::Submit);
+        assert_eq!(line.as_str(), "x ");
+
+        line.clear();
+        assert_eq!(line.as_str(), "");
+    }
+
+    #[kernel_test]
+    fn test_line_full() {
+        let mut line = LineEditor::new();
+
+        for _ in 0..LINE_SIZE {
+            assert_eq!(line.feed(b'a'), Edit::Insert(b'a'));
+        }
+
+        assert_eq!(line.feed(b'a'), Edit::Ignore);
+        assert_eq!(line.as_str().len(), LINE_SIZE);
+    }
+}
@@ -0,0 +1,550 @@
+mod command;
+mod line;
+
+pub use command::{Command, MAX_WRITE};
+
+use line::{Edit, LineEditor};
+
+use super::memory;
+use super::trap::{TrapFrame, BREAKPOINT_VECTOR, DEBUG_VECTOR, TRAP_FLAG};
+use crate::driver::keyboard;
+use crate::driver::serial::{self, ComPort, Config, SerialError};
+use crate::libs::backtrace::{self, Backtrace, Frame, Frames};
+use crate::libs::dmesg;
+use crate::nucleus::interrupt::idt::{
+    InterruptDescriptorOptions, InterruptDescriptorTableEntry, InterruptGateType,
+};
+use crate::nucleus::sync::IrqMutex;
+use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicBool, Ordering};
+use x86_64::structures::paging::PageTableFlags;
+use x86_64::VirtAddr;
+
+/// Ctrl-B on the serial port breaks into the monitor.
+pub const SERIAL_BREAK: u8 = 0x02;
+
+/// Scroll Lock on the keyboard breaks into the monitor.
+pub const KEYBOARD_BREAK: u8 = 0x46;
+
+const MAX_EXAMINE: usize = 0x1000;
+
+/// Size of a long mode IDT entry. `InterruptDescriptorTableEntry` covers the first 12 bytes.
+const IDT_ENTRY_SIZE: u64 = 16;
+
+const HELP: &str = "\
+r                 show registers
+x addr [len]      hexdump memory
+w addr byte...    write bytes to memory
+d [addr] [len]    hexdump code, at rip by default
+pt addr           walk the page tables for an address
+idt               list the loaded interrupt descriptors
+bt                backtrace from the trapped frame
+dmesg [prev]      kernel messages, of this or the previous boot
+s                 single-step
+c                 continue
+numbers are hexadecimal
+";
+
+static MONITOR: IrqMutex<Option<Monitor>> = IrqMutex::new(None);
+static BREAK_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    Serial(ComPort),
+    Keyboard,
+}
+
+impl Input {
+    fn read_byte(self) -> u8 {
+        loop {
+            let byte = match self {
+                Self::Serial(port) => serial::read_byte(port),
+                Self::Keyboard => keyboard::read_byte(),
+            };
+
+            if let Some(byte) = byte {
+                return byte;
+            }
+
+            core::hint::spin_loop();
+        }
+    }
+}
+
+/// Answers on the device the commands come from: the serial port, or the screen for the keyboard.
+struct Terminal(Input);
+
+impl Write for Terminal {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        match self.0 {
+            Input::Serial(port) => {
+                serial::write_fmt(port, format_args!("{}", string)).ok();
+            }
+            Input::Keyboard => crate::driver::vga::print_screen(format_args!("{}", string)),
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Stay,
+    Continue,
+    Step,
+}
+
+struct Monitor {
+    input: Input,
+    line: LineEditor,
+}
+
+impl Monitor {
+    const fn new(input: Input) -> Self {
+        Self {
+            input,
+            line: LineEditor::new(),
+        }
+    }
+
+    fn enter(&mut self, frame: &mut TrapFrame) {
+        let mut terminal = Terminal(self.input);
+        frame.rflags &= !TRAP_FLAG;
+
+        let reason = match frame.vector {
+            BREAKPOINT_VECTOR => "breakpoint",
+            _ => "stopped",
+        };
+
+        writeln!(terminal, "\nmonitor: {} at {}", reason, locate(frame.rip)).ok();
+
+        loop {
+            let line = self.read_line(&mut terminal);
+
+            match execute(Command::parse(line), frame, &mut terminal) {
+                Action::Stay => {}
+                Action::Continue => return,
+                Action::Step => {
+                    BREAK_REQUESTED.store(true, Ordering::SeqCst);
+                    frame.rflags |= TRAP_FLAG;
+                    return;
+                }
+            }
+        }
+    }
+
+    fn read_line(&mut self, terminal: &mut Terminal) -> &str {
+        self.line.clear();
+        terminal.write_str("monitor> ").ok();
+
+        loop {
+            match self.line.feed(self.input.read_byte()) {
+                Edit::Insert(byte) => terminal.write_char(byte as char),
+                Edit::Erase => terminal.write_str("\x08 \x08"),
+                Edit::Submit => break,
+                Edit::Ignore => Ok(()),
+            }
+            .ok();
+        }
+
+        terminal.write_char('\n').ok();
+        self.line.as_str()
+    }
+}
+
+fn execute<W: Write>(command: Command, frame: &mut TrapFrame, out: &mut W) -> Action {
+    let result = match command {
+        Command::Registers => write_registers(out, frame),
+        Command::Examine { address, length } => {
+            examine(out, address, core::cmp::min(length, MAX_EXAMINE))
+        }
+        Command::Modify {
+            address,
+            bytes,
+            length,
+        } => match memory::is_mapped(address, length) {
+            true => {
+                unsafe { memory::write(address, &bytes[..length]) };
+                writeln!(out, "wrote {} bytes at {:#x}", length, address)
+            }
+            false => writeln!(out, "{:#x} is not mapped", address),
+        },
+        Command::Code { address, length } => {
+            let address = address.unwrap_or(frame.rip);
+
+            writeln!(out, "{}", locate(address))
+                .and_then(|_| examine(out, address, core::cmp::min(length, MAX_EXAMINE)))
+        }
+        Command::PageWalk(address) => walk_page_tables(out, address),
+        Command::InterruptTable => write_interrupt_table(out),
+        Command::Backtrace => {
+            let frames = unsafe { Frames::from_frame_pointer(frame.rbp) }
+                .validated(|frame_pointer| memory::is_mapped(frame_pointer, 16));
+            write!(out, "{}", Backtrace::from_frames(frames))
+        }
+        Command::Dmesg { previous: false } => dmesg::read(out),
+        Command::Dmesg { previous: true } => match dmesg::read_previous(out) {
+            Some(result) => result,
+            None => writeln!(out, "no messages from the previous boot"),
+        },
+        Command::Step => return Action::Step,
+        Command::Continue => return Action::Continue,
+        Command::Help => out.write_str(HELP),
+        Command::Empty => Ok(()),
+        Command::Malformed => writeln!(out, "malformed command, try help"),
+        Command::Unknown => writeln!(out, "unknown command, try help"),
+    };
+
+    result.ok();
+    Action::Stay
+}
+
+fn locate(address: u64) -> Frame {
+    Frame {
+        address,
+        symbol: backtrace::embedded().and_then(|table| table.lookup(address)),
+    }
+}
+
+fn write_registers<W: Write>(out: &mut W, frame: &TrapFrame) -> fmt::Result {
+    let registers = [
+        ("rip", frame.rip),
+        ("rsp", frame.rsp),
+        ("rflags", frame.rflags),
+        ("rax", frame.rax),
+        ("rbx", frame.rbx),
+        ("rcx", frame.rcx),
+        ("rdx", frame.rdx),
+        ("rsi", frame.rsi),
+        ("rdi", frame.rdi),
+        ("rbp", frame.rbp),
+        ("r8", frame.r8),
+        ("r9", frame.r9),
+        ("r10", frame.r10),
+        ("r11", frame.r11),
+        ("r12", frame.r12),
+        ("r13", frame.r13),
+        ("r14", frame.r14),
+        ("r15", frame.r15),
+        ("cs", frame.cs),
+        ("ss", frame.ss),
+    ];
+
+    for row in registers.chunks(3) {
+        for (name, value) in row {
+            write!(out, "{:>6} {:016x}", name, value)?;
+        }
+
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+fn examine<W: Write>(out: &mut W, address: u64, length: usize) -> fmt::Result {
+    if !memory::is_mapped(address, length) {
+        return writeln!(out, "{:#x} is not mapped", address);
+    }
+
+    let mut bytes = [0; 256];
+
+    for offset in (0..length).step_by(bytes.len()) {
+        let chunk = &mut bytes[..core::cmp::min(256, length - offset)];
+        unsafe { memory::read(address + offset as u64, chunk) };
+        write_hexdump(out, address + offset as u64, chunk)?;
+    }
+
+    Ok(())
+}
+
+/// Sixteen bytes per line, followed by their printable characters.
+fn write_hexdump<W: Write>(out: &mut W, address: u64, bytes: &[u8]) -> fmt::Result {
+    for (index, line) in bytes.chunks(16).enumerate() {
+        write!(out, "{:016x} ", address + index as u64 * 16)?;
+
+        for column in 0..16 {
+            match line.get(column) {
+                Some(byte) => write!(out, " {:02x}", byte)?,
+                None => out.write_str("   ")?,
+            }
+        }
+
+        out.write_str("  ")?;
+
+        for byte in line {
+            match byte {
+                b' '..=b'~' => out.write_char(*byte as char)?,
+                _ => out.write_char('.')?,
+            }
+        }
+
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+fn walk_page_tables<W: Write>(out: &mut W, address: u64) -> fmt::Result {
+    let virtual_address = match VirtAddr::try_new(address) {
+        Ok(virtual_address) => virtual_address,
+        Err(_) => return writeln!(out, "{:#x} is not canonical", address),
+    };
+
+    let mut result = Ok(());
+    let mut physical = None;
+
+    let walked = memory::walk(virtual_address, |level, index, entry| {
+        let name = match level {
+            4 => "PML4",
+            3 => "PDPT",
+            2 => "PD",
+            _ => "PT",
+        };
+
+        let page_size = 1u64 << (12 + 9 * (level as u64 - 1));
+
+        physical = match entry.flags().contains(PageTableFlags::PRESENT) {
+            true => Some(entry.addr().as_u64() + (address & (page_size - 1))),
+            false => None,
+        };
+
+        result = result.and_then(|_| {
+            writeln!(
+                out,
+                "{:>4}[{:#05x}] {:#018x} {:?}",
+                name,
+                index,
+                entry.addr().as_u64(),
+                entry.flags()
+            )
+        });
+    });
+
+    result?;
+
+    match (walked, physical) {
+        (false, _) => writeln!(
+            out,
+            "page tables unavailable without the physical memory offset"
+        ),
+        (true, Some(physical)) => {
+            writeln!(out, "{:#x} -> {:#x}", address, physical)
+        }
+        (true, _) => writeln!(out, "{:#x} is not mapped", address),
+    }
+}
+
+fn write_interrupt_table<W: Write>(out: &mut W) -> fmt::Result {
+    let pointer = x86_64::instructions::tables::sidt();
+    let count = (pointer.limit as u64 + 1) / IDT_ENTRY_SIZE;
+
+    for vector in 0..count {
+        let address = pointer.base.as_u64() + vector * IDT_ENTRY_SIZE;
+        let entry =
+            unsafe { core::ptr::read_unaligned(address as *const InterruptDescriptorTableEntry) };
+
+        if !InterruptDescriptorOptions::is_valid(entry.options) {
+            writeln!(out, "{:>3} invalid options {:#06x}", vector, entry.options)?;
+            continue;
+        }
+
+        let options = InterruptDescriptorOptions::from(entry.options);
+
+        if !options.present {
+            continue;
+        }
+
+        let gate = match options.gate_type {
+            InterruptGateType::Trap => "trap",
+            InterruptGateType::Interrupt => "interrupt",
+        };
+
+        writeln!(
+            out,
+            "{:>3} {} selector {:#06x} {} dpl {} ist {}",
+            vector,
+            locate(entry.handler_address()),
+            entry.global_descriptor_selector,
+            gate,
+            options.descriptor_privilege_level,
+            options.interrupt_stack_table
+        )?;
+    }
+
+    Ok(())
+}
+
+fn serial_break(port: ComPort, byte: u8) -> bool {
+    byte == SERIAL_BREAK && request_break(Input::Serial(port))
+}
+
+fn keyboard_break(scancode: u8) -> bool {
+    scancode == KEYBOARD_BREAK && request_break(Input::Keyboard)
+}
+
+fn request_break(input: Input) -> bool {
+    let listening = MONITOR.try_lock().is_some_and(|monitor| {
+        monitor
+            .as_ref()
+            .is_some_and(|monitor| monitor.input == input)
+    });
+
+    if listening {
+        BREAK_REQUESTED.store(true, Ordering::SeqCst);
+    }
+
+    listening
+}
+
+/// Runs the monitor on every breakpoint, and when the break key arrives from `input`: Ctrl-B over
+/// serial, or Scroll Lock on the keyboard. The break key needs the PIC initialised and interrupts
+/// enabled. Page walks and memory checks use the bootloader's physical memory mapping.
+pub fn init(input: Input, physical_memory_offset: u64) -> Result<(), SerialError> {
+    match input {
+        Input::Serial(port) => {
+            if serial::with_port(port, |_| ()).is_none() {
+                serial::init(port, Config::new())?;
+            }
+
+            serial::set_break_hook(Some(serial_break));
+            serial::enable_receive_interrupts(port)?;
+        }
+        Input::Keyboard => {
+            keyboard::set_break_hook(Some(keyboard_break));
+            keyboard::enable_interrupts();
+        }
+    }
+
+    memory::set_physical_memory_offset(physical_memory_offset);
+
+    *MONITOR.lock() = Some(Monitor::new(input));
+    Ok(())
+}
+
+/// Runs the monitor for a breakpoint, a break key or a step it asked for. Returns `false` when
+/// no monitor is set up, or when the trap came from the monitor itself.
+pub(super) fn handle_trap(frame: &mut TrapFrame) -> bool {
+    let requested = BREAK_REQUESTED.swap(false, Ordering::SeqCst);
+
+    if frame.vector == DEBUG_VECTOR && !requested {
+        return false;
+    }
+
+    let mut monitor = match MONITOR.try_lock() {
+        Some(monitor) => monitor,
+        None => return false,
+    };
+
+    match monitor.as_mut() {
+        Some(monitor) => {
+            monitor.enter(frame);
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::format::FixedString;
+    use crate::libs::testing::kernel_test;
+
+    fn run(line: &str, frame: &mut TrapFrame) -> (Action, FixedString<1024>) {
+        let mut output = FixedString::new();
+        let action = execute(Command::parse(line), frame, &mut output);
+        (action, output)
+    }
+
+    #[kernel_test]
+    fn test_registers() {
+        let mut frame = TrapFrame {
+            rip: 0x20_1a3c,
+            r15: 0xdead,
+            ..TrapFrame::default()
+        };
+
+        let (action, output) = run("r", &mut frame);
+
+        assert_eq!(action, Action::Stay);
+        assert!(output
+            .as_str()
+            .starts_with("   rip 0000000000201a3c   rsp 0000000000000000"));
+        assert!(output.as_str().contains("   r15 000000000000dead\n"));
+        assert_eq!(output.as_str().lines().count(), 7);
+    }
+
+    #[kernel_test]
+    fn test_examine() {
+        let bytes = *b"ferros\x00\x01monitor\xff";
+        let address = bytes.as_ptr() as u64;
+        let mut frame = TrapFrame::default();
+
+        let (_, output) = run("x 0 0", &mut frame);
+        assert_eq!(output.as_str(), "");
+
+        let mut expected = FixedString::<128>::new();
+        writeln!(
+            expected,
+            "{:016x}  66 65 72 72 6f 73 00 01 6d 6f 6e 69 74 6f 72 ff  ferros..monitor.",
+            address
+        )
+        .ok();
+
+        let mut line = FixedString::<32>::new();
+        write!(line, "x {:x} 10", address).ok();
+
+        let (_, output) = run(line.as_str(), &mut frame);
+        assert_eq!(output.as_str(), expected.as_str());
+    }
+
+    #[kernel_test]
+    fn test_hexdump_partial_line() {
+        let mut output = FixedString::<128>::new();
+        write_hexdump(&mut output, 0x1000, b"abc").ok();
+
+        assert_eq!(
+            output.as_str(),
+            "0000000000001000  61 62 63                                         abc\n"
+        );
+    }
+
+    #[kernel_test]
+    fn test_resume() {
+        let mut frame = TrapFrame::default();
+
+        assert_eq!(run("c", &mut frame).0, Action::Continue);
+        assert_eq!(run("s", &mut frame).0, Action::Step);
+        assert_eq!(run("", &mut frame).0, Action::Stay);
+        assert_eq!(
+            run("frobnicate", &mut frame).1.as_str(),
+            "unknown command, try help\n"
+        );
+    }
+
+    /// Keeps the end of the output, since the ring holds everything logged before the test.
+    struct Tail([u8; 32]);
+
+    impl Write for Tail {
+        fn write_str(&mut self, string: &str) -> fmt::Result {
+            for byte in string.bytes() {
+                self.0.rotate_left(1);
+                self.0[31] = byte;
+            }
+
+            Ok(())
+        }
+    }
+
+    #[kernel_test]
+    fn test_dmesg() {
+        let mut frame = TrapFrame::default();
+        let mut tail = Tail([0; 32]);
+        dmesg::write(b"last message before the monitor\n");
+
+        let action = execute(Command::parse("dmesg"), &mut frame, &mut tail);
+
+        assert_eq!(action, Action::Stay);
+        assert_eq!(&tail.0, b"last message before the monitor\n");
+    }
+}
@@ -0,0 +1,225 @@
+use super::{crashdump, gdbstub, monitor, watchpoint};
+use crate::{emergency_println, println};
+use x86_64::registers::debug::{Dr6, Dr6Flags};
+use x86_64::registers::rflags::RFlags;
+use x86_64::structures::idt::InterruptStackFrame;
+
+pub const DEBUG_VECTOR: u64 = 1;
+pub const BREAKPOINT_VECTOR: u64 = 3;
+
+const DIVIDE_ERROR_VECTOR: u64 = 0;
+const INVALID_OPCODE_VECTOR: u64 = 6;
+const DOUBLE_FAULT_VECTOR: u64 = 8;
+const GENERAL_PROTECTION_VECTOR: u64 = 13;
+const PAGE_FAULT_VECTOR: u64 = 14;
+
+/// RFLAGS.TF: raise a debug exception after the next instruction.
+pub const TRAP_FLAG: u64 = 1 << 8;
+
+/// DR6 with no conditions reported. The processor sets its bits but never clears them.
+const DR6_CLEAR: u64 = 0xffff_0ff0;
+
+/// Registers saved on entry to a debug trap, in stack order. Changes are restored on return.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub struct TrapFrame {
+    pub r15: u64,
+    pub r14: u64,
+    pub r13: u64,
+    pub r12: u64,
+    pub r11: u64,
+    pub r10: u64,
+    pub r9: u64,
+    pub r8: u64,
+    pub rbp: u64,
+    pub rdi: u64,
+    pub rsi: u64,
+    pub rdx: u64,
+    pub rcx: u64,
+    pub rbx: u64,
+    pub rax: u64,
+    pub vector: u64,
+    pub error_code: u64,
+    pub rip: u64,
+    pub cs: u64,
+    pub rflags: u64,
+    pub rsp: u64,
+    pub ss: u64,
+}
+
+/// Sets TF in an interrupt handler's frame, so the interrupted code takes a debug trap after its
+/// next instruction. This is how a hotkey breaks in with the registers of the code it interrupted.
+pub fn trap_on_return(stack_frame: &mut InterruptStackFrame) {
+    unsafe {
+        stack_frame
+            .as_mut()
+            .update(|frame| frame.cpu_flags.insert(RFlags::TRAP_FLAG))
+    };
+}
+
+/// IDT entry for the debug exception.
+#[unsafe(naked)]
+pub(crate) unsafe extern "C" fn debug_entry() {
+    core::arch::naked_asm!(
+        "push 0",
+        "push {vector}",
+        "jmp {common}",
+        vector = const DEBUG_VECTOR,
+        common = sym trap_entry,
+    )
+}
+
+/// IDT entry for the breakpoint exception.
+#[unsafe(naked)]
+pub(crate) unsafe extern "C" fn breakpoint_entry() {
+    core::arch::naked_asm!(
+        "push 0",
+        "push {vector}",
+        "jmp {common}",
+        vector = const BREAKPOINT_VECTOR,
+        common = sym trap_entry,
+    )
+}
+
+/// Declares the IDT entry of a fatal fault. Faults with an error code only push the vector.
+macro_rules! fault_entry {
+    ($name:ident, $vector:expr) => {
+        #[unsafe(naked)]
+        pub(crate) unsafe extern "C" fn $name() {
+            core::arch::naked_asm!(
+                "push 0",
+                "push {vector}",
+                "jmp {common}",
+                vector = const $vector,
+                common = sym trap_entry,
+            )
+        }
+    };
+    ($name:ident, $vector:expr, error_code) => {
+        #[unsafe(naked)]
+        pub(crate) unsafe extern "C" fn $name() {
+            core::arch::naked_asm!(
+                "push {vector}",
+                "jmp {common}",
+                vector = const $vector,
+                common = sym trap_entry,
+            )
+        }
+    };
+}
+
+fault_entry!(divide_error_entry, DIVIDE_ERROR_VECTOR);
+fault_entry!(invalid_opcode_entry, INVALID_OPCODE_VECTOR);
+fault_entry!(double_fault_entry, DOUBLE_FAULT_VECTOR, error_code);
+fault_entry!(
+    general_protection_entry,
+    GENERAL_PROTECTION_VECTOR,
+    error_code
+);
+fault_entry!(page_fault_entry, PAGE_FAULT_VECTOR, error_code);
+
+/// Saves the general purpose registers below the vector and error code, so the stack holds a
+/// `TrapFrame`. The CPU aligned the stack before pushing its frame and the frame is a multiple of
+/// 16 bytes, so the call is aligned too.
+#[unsafe(naked)]
+unsafe extern "C" fn trap_entry() {
+    core::arch::naked_asm!(
+        "push rax",
+        "push rbx",
+        "push rcx",
+        "push rdx",
+        "push rsi",
+        "push rdi",
+        "push rbp",
+        "push r8",
+        "push r9",
+        "push r10",
+        "push r11",
+        "push r12",
+        "push r13",
+        "push r14",
+        "push r15",
+        "mov rdi, rsp",
+        "cld",
+        "call {handler}",
+        "pop r15",
+        "pop r14",
+        "pop r13",
+        "pop r12",
+        "pop r11",
+        "pop r10",
+        "pop r9",
+        "pop r8",
+        "pop rbp",
+        "pop rdi",
+        "pop rsi",
+        "pop rdx",
+        "pop rcx",
+        "pop rbx",
+        "pop rax",
+        "add rsp, 16",
+        "iretq",
+        handler = sym handle_trap,
+    )
+}
+
+extern "C" fn handle_trap(frame: &mut TrapFrame) {
+    if frame.vector != DEBUG_VECTOR && frame.vector != BREAKPOINT_VECTOR {
+        handle_fault(frame);
+    }
+
+    if frame.vector == DEBUG_VECTOR && watchpoint::handle_trap(frame, take_debug_status()) {
+        return;
+    }
+
+    if gdbstub::handle_trap(frame) || monitor::handle_trap(frame) {
+        return;
+    }
+
+    match frame.vector {
+        BREAKPOINT_VECTOR => println!("EXCEPTION: BREAKPOINT\n{:#x?}", frame),
+        _ => {
+            frame.rflags &= !TRAP_FLAG;
+            println!("EXCEPTION: DEBUG\n{:#x?}", frame);
+        }
+    }
+}
+
+/// Reads DR6 and resets it, so the next debug exception only reports its own conditions.
+fn take_debug_status() -> Dr6Flags {
+    let status = Dr6::read();
+    unsafe { core::arch::asm!("mov dr6, {}", in(reg) DR6_CLEAR, options(nomem, nostack)) };
+    status
+}
+
+fn fault_name(vector: u64) -> &'static str {
+    match vector {
+        DIVIDE_ERROR_VECTOR => "divide error",
+        INVALID_OPCODE_VECTOR => "invalid opcode",
+        DOUBLE_FAULT_VECTOR => "double fault",
+        GENERAL_PROTECTION_VECTOR => "general protection fault",
+        PAGE_FAULT_VECTOR => "page fault",
+        _ => "unknown fault",
+    }
+}
+
+/// Reports a fault the kernel cannot recover from, writes a crash dump and panics.
+fn handle_fault(frame: &TrapFrame) -> ! {
+    let name = fault_name(frame.vector);
+
+    emergency_println!("EXCEPTION: {}\n{:#x?}", name, frame);
+    crashdump::write(name, frame);
+
+    panic!("fatal {} at {:#x}", name, frame.rip);
+}
+
+#[cfg(all(test, target_os = "none"))]
+mod tests {
+    #[test_case]
+    fn test_breakpoint_returns() {
+        let value = core::hint::black_box(41);
+        x86_64::instructions::interrupts::int3();
+
+        assert_eq!(core::hint::black_box(value) + 1, 42);
+    }
+}
@@ -0,0 +1,345 @@
+use super::trap::{TrapFrame, DEBUG_VECTOR};
+use crate::nucleus::sync::IrqMutex;
+use x86_64::registers::debug::{
+    BreakpointCondition, BreakpointSize, DebugAddressRegister, DebugAddressRegisterNumber, Dr0,
+    Dr1, Dr2, Dr3, Dr6Flags, Dr7, Dr7Flags, Dr7Value,
+};
+use x86_64::registers::rflags::RFlags;
+use x86_64::VirtAddr;
+
+/// One per debug address register, DR0 to DR3.
+pub const MAX_WATCHPOINTS: usize = 4;
+
+static SLOTS: IrqMutex<[Option<Slot>; MAX_WATCHPOINTS]> = IrqMutex::new([None; MAX_WATCHPOINTS]);
+
+/// Called on the debug exception with the watchpoint that fired and the registers of the code
+/// that fired it. Changes to the frame are restored on return.
+pub type Callback = fn(&Watchpoint, &mut TrapFrame);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchKind {
+    /// Traps before the instruction at the address runs.
+    Execute,
+    /// Traps after an instruction wrote to the range.
+    Write,
+    /// Traps after an instruction read or wrote the range.
+    ReadWrite,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchpointError {
+    Full,
+    NotCanonical,
+    InvalidLength(usize),
+    Misaligned,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchpointId(DebugAddressRegisterNumber);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Watchpoint {
+    pub address: u64,
+    pub kind: WatchKind,
+    pub length: usize,
+}
+
+impl Watchpoint {
+    /// The processor watches naturally aligned ranges of 1, 2, 4 or 8 bytes. Execute watchpoints
+    /// always have length 1.
+    pub fn new(address: u64, kind: WatchKind, length: usize) -> Result<Self, WatchpointError> {
+        if VirtAddr::try_new(address).is_err() {
+            return Err(WatchpointError::NotCanonical);
+        }
+
+        if BreakpointSize::new(length).is_none() || (kind == WatchKind::Execute && length != 1) {
+            return Err(WatchpointError::InvalidLength(length));
+        }
+
+        if !address.is_multiple_of(length as u64) {
+            return Err(WatchpointError::Misaligned);
+        }
+
+        Ok(Self {
+            address,
+            kind,
+            length,
+        })
+    }
+
+    fn condition(&self) -> BreakpointCondition {
+        match self.kind {
+            WatchKind::Execute => BreakpointCondition::InstructionExecution,
+            WatchKind::Write => BreakpointCondition::DataWrites,
+            WatchKind::ReadWrite => BreakpointCondition::DataReadsWrites,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Slot {
+    watchpoint: Watchpoint,
+    callback: Callback,
+}
+
+/// Sets the DR7 fields of one debug address register: enabled with the watchpoint's condition
+/// and length, or disabled.
+fn encode(dr7: &mut Dr7Value, number: DebugAddressRegisterNumber, watchpoint: Option<&Watchpoint>) {
+    let enable = Dr7Flags::local_breakpoint_enable(number);
+
+    match watchpoint {
+        Some(watchpoint) => {
+            dr7.set_condition(number, watchpoint.condition());
+            dr7.set_size(number, BreakpointSize::new(watchpoint.length).unwrap());
+            dr7.insert_flags(enable);
+        }
+        None => {
+            dr7.remove_flags(enable);
+            dr7.set_condition(number, BreakpointCondition::InstructionExecution);
+            dr7.set_size(number, BreakpointSize::Length1B);
+        }
+    }
+}
+
+fn number(index: usize) -> DebugAddressRegisterNumber {
+    DebugAddressRegisterNumber::new(index as u8).unwrap()
+}
+
+fn write_address(number: DebugAddressRegisterNumber, address: u64) {
+    match number {
+        DebugAddressRegisterNumber::Dr0 => Dr0::write(address),
+        DebugAddressRegisterNumber::Dr1 => Dr1::write(address),
+        DebugAddressRegisterNumber::Dr2 => Dr2::write(address),
+        DebugAddressRegisterNumber::Dr3 => Dr3::write(address),
+    }
+}
+
+/// Watches a range in a free debug address register. `callback` runs on every hit.
+pub fn set(
+    address: u64,
+    kind: WatchKind,
+    length: usize,
+    callback: Callback,
+) -> Result<WatchpointId, WatchpointError> {
+    let watchpoint = Watchpoint::new(address, kind, length)?;
+    let mut slots = SLOTS.lock();
+
+    let index = slots
+        .iter()
+        .position(|slot| slot.is_none())
+        .ok_or(WatchpointError::Full)?;
+
+    let number = number(index);
+    slots[index] = Some(Slot {
+        watchpoint,
+        callback,
+    });
+
+    write_address(number, address);
+    Dr7::update(|dr7| encode(dr7, number, Some(&watchpoint)));
+
+    Ok(WatchpointId(number))
+}
+
+pub fn clear(id: WatchpointId) {
+    let mut slots = SLOTS.lock();
+
+    Dr7::update(|dr7| encode(dr7, id.0, None));
+    write_address(id.0, 0);
+    slots[id.0.get() as usize] = None;
+}
+
+pub fn clear_all() {
+    let mut slots = SLOTS.lock();
+
+    for (index, slot) in slots.iter_mut().enumerate() {
+        Dr7::update(|dr7| encode(dr7, number(index), None));
+        *slot = None;
+    }
+}
+
+pub fn get(id: WatchpointId) -> Option<Watchpoint> {
+    SLOTS.lock()[id.0.get() as usize].map(|slot| slot.watchpoint)
+}
+
+/// Runs the callbacks of the watchpoints `status`, the DR6 of this exception, reports. Returns
+/// `false` when none fired, or when a single step is also pending, so the step still reaches its
+/// debugger.
+pub(super) fn handle_trap(frame: &mut TrapFrame, status: Dr6Flags) -> bool {
+    debug_assert_eq!(frame.vector, DEBUG_VECTOR);
+
+    let slots = match SLOTS.try_lock() {
+        Some(slots) => *slots,
+        None => return false,
+    };
+
+    let mut fired = false;
+
+    for (index, slot) in slots.iter().enumerate() {
+        let slot = match slot {
+            Some(slot) if status.contains(Dr6Flags::trap(number(index))) => slot,
+            _ => continue,
+        };
+
+        fired = true;
+        (slot.callback)(&slot.watchpoint, frame);
+
+        // An execute watchpoint traps before the instruction, so it would fire again on return.
+        if slot.watchpoint.kind == WatchKind::Execute {
+            frame.rflags |= RFlags::RESUME_FLAG.bits();
+        }
+    }
+
+    fired && !status.contains(Dr6Flags::STEP)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+    #[cfg(target_os = "none")]
+    use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+    #[kernel_test]
+    fn test_new_watchpoint() {
+        assert!(Watchpoint::new(0x1000, WatchKind::Write, 8).is_ok());
+        assert!(Watchpoint::new(0x1001, WatchKind::Execute, 1).is_ok());
+        assert_eq!(
+            Watchpoint::new(0x1000, WatchKind::Write, 3),
+            Err(WatchpointError::InvalidLength(3))
+        );
+        assert_eq!(
+            Watchpoint::new(0x1000, WatchKind::Execute, 4),
+            Err(WatchpointError::InvalidLength(4))
+        );
+        assert_eq!(
+            Watchpoint::new(0x1002, WatchKind::ReadWrite, 4),
+            Err(WatchpointError::Misaligned)
+        );
+        assert_eq!(
+            Watchpoint::new(0x8000_0000_0000, WatchKind::Write, 1),
+            Err(WatchpointError::NotCanonical)
+        );
+    }
+
+    #[kernel_test]
+    fn test_encode() {
+        let mut dr7 = Dr7Value::from(Dr7Flags::empty());
+        let watchpoint = Watchpoint::new(0x1000, WatchKind::Write, 4).unwrap();
+
+        encode(&mut dr7, DebugAddressRegisterNumber::Dr2, Some(&watchpoint));
+        assert_eq!(dr7.bits(), 0b1101 << 24 | 1 << 4);
+
+        let watchpoint = Watchpoint::new(0x2000, WatchKind::ReadWrite, 8).unwrap();
+        encode(&mut dr7, DebugAddressRegisterNumber::Dr0, Some(&watchpoint));
+        assert_eq!(dr7.bits(), 0b1101 << 24 | 0b1011 << 16 | 1 << 4 | 1);
+
+        encode(&mut dr7, DebugAddressRegisterNumber::Dr2, None);
+        assert_eq!(dr7.bits(), 0b1011 << 16 | 1);
+    }
+
+    #[cfg(target_os = "none")]
+    static HITS: AtomicUsize = AtomicUsize::new(0);
+    #[cfg(target_os = "none")]
+    static WATCHED: AtomicU64 = AtomicU64::new(0);
+
+    #[cfg(target_os = "none")]
+    fn count(_watchpoint: &Watchpoint, _frame: &mut TrapFrame) {
+        HITS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[cfg(target_os = "none")]
+    #[inline(never)]
+    fn watched_function() -> u64 {
+        core::hint::black_box(42)
+    }
+
+    #[cfg(target_os = "none")]
+    #[test_case]
+    fn test_write_watchpoint() {
+        HITS.store(0, Ordering::SeqCst);
+        let address = &WATCHED as *const AtomicU64 as u64;
+        let id = set(address, WatchKind::Write, 8, count).unwrap();
+
+        WATCHED.load(Ordering::SeqCst);
+        assert_eq!(HITS.load(Ordering::SeqCst), 0);
+
+        WATCHED.store(1, Ordering::SeqCst);
+        assert_eq!(HITS.load(Ordering::SeqCst), 1);
+
+        clear(id);
+        WATCHED.store(2, Ordering::SeqCst);
+        assert_eq!(HITS.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(target_os = "none")]
+    #[test_case]
+    fn test_execute_watchpoint() {
+        HITS.store(0, Ordering::SeqCst);
+        let id = set(
+            watched_function as *const () as u64,
+            WatchKind::Execute,
+            1,
+            count,
+        )
+        .unwrap();
+
+        assert_eq!(watched_function(), 42);
+        assert_eq!(watched_function(), 42);
+        assert_eq!(HITS.load(Ordering::SeqCst), 2);
+
+        clear(id);
+    }
+
+    #[cfg(target_os = "none")]
+    #[test_case]
+    fn test_watchpoint_after_single_step() {
+        HITS.store(0, Ordering::SeqCst);
+
+        unsafe {
+            core::arch::asm!(
+                "pushfq",
+                "or qword ptr [rsp], {flag}",
+                "popfq",
+                "nop",
+                "nop",
+                flag = const crate::nucleus::debug::TRAP_FLAG,
+            )
+        };
+        assert!(!x86_64::registers::debug::Dr6::read().contains(Dr6Flags::STEP));
+
+        let address = &WATCHED as *const AtomicU64 as u64;
+        let id = set(address, WatchKind::Write, 8, count).unwrap();
+        let mut frame = TrapFrame {
+            vector: DEBUG_VECTOR,
+            ..TrapFrame::default()
+        };
+
+        let fired = Dr6Flags::trap(id.0);
+        assert!(handle_trap(&mut frame, fired));
+        assert!(!handle_trap(&mut frame, fired | Dr6Flags::STEP));
+
+        WATCHED.store(3, Ordering::SeqCst);
+        assert_eq!(HITS.load(Ordering::SeqCst), 3);
+
+        clear(id);
+    }
+
+    #[cfg(target_os = "none")]
+    #[test_case]
+    fn test_full() {
+        let mut ids = [None; MAX_WATCHPOINTS];
+
+        for (index, id) in ids.iter_mut().enumerate() {
+            *id = Some(set(0x1000 + index as u64 * 8, WatchKind::Write, 8, count).unwrap());
+        }
+
+        assert_eq!(
+            set(0x2000, WatchKind::Write, 8, count),
+            Err(WatchpointError::Full)
+        );
+
+        clear_all();
+        assert_eq!(ids[0].and_then(get), None);
+    }
+}
@@ -47,6 +47,12 @@ impl InterruptDescriptorTableEntry {
             function_pointer_high: 0,
         }
     }
+
+    pub const fn handler_address(&self) -> u64 {
+        self.function_pointer_low as u64
+            | (self.function_pointer_middle as u64) << 16
+            | (self.function_pointer_high as u64) << 32
+    }
 }
 
 impl Default for InterruptDescriptorTableEntry {
@@ -145,6 +151,13 @@ impl InterruptDescriptorOptions {
     }
 }
 
+impl InterruptDescriptorOptions {
+    /// Whether the reserved bits hold the values `From<u16>` insists on.
+    pub const fn is_valid(options: u16) -> bool {
+        options & 0x1000 == 0 && options & 0x0C00 == 0x0C00
+    }
+}
+
 impl Default for InterruptDescriptorOptions {
     fn default() -> Self {
         Self::empty()
@@ -246,9 +259,7 @@ impl<const N: usize> Default for InterruptDescriptorTable<N> {
 }
 
 extern "C" fn handle_zero_division() -> ! {
-    use crate::println;
-
-    println!("Handling zero division");
+    crate::emergency_println!("Handling zero division");
 
     loop {}
 }
@@ -260,3 +271,43 @@ lazy_static::lazy_static!(
         idt
     };
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::testing::kernel_test;
+
+    #[kernel_test]
+    fn test_options_as_u16() {
+        let interrupt = InterruptDescriptorOptions::new(true, 0, InterruptGateType::Interrupt, 0);
+        let trap = InterruptDescriptorOptions::new(true, 3, InterruptGateType::Trap, 2);
+
+        assert_eq!(interrupt.as_u16(), 0x8e00);
+        assert_eq!(trap.as_u16(), 0xef02);
+        assert_eq!(InterruptDescriptorOptions::empty().as_u16(), 0x0e00);
+    }
+
+    #[kernel_test]
+    fn test_options_from_u16() {
+        let options = InterruptDescriptorOptions::from(0xef02);
+
+        assert!(options.present);
+        assert_eq!(options.descriptor_privilege_level, 3);
+        assert!(matches!(options.gate_type, InterruptGateType::Trap));
+        assert_eq!(options.interrupt_stack_table, 2);
+        assert_eq!(options.as_u16(), 0xef02);
+    }
+
+    #[kernel_test]
+    fn test_options_is_valid() {
+        assert!(InterruptDescriptorOptions::is_valid(0xef02));
+        assert!(InterruptDescriptorOptions::is_valid(0x0e00));
+        assert!(!InterruptDescriptorOptions::is_valid(0x8000));
+        assert!(!InterruptDescriptorOptions::is_valid(0x9e00));
+    }
+
+    #[kernel_test(should_panic(expected = "Ones are not set"))]
+    fn test_options_from_invalid_u16() {
+        let _ = InterruptDescriptorOptions::from(0x8000);
+    }
+}
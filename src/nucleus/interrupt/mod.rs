@@ -1,18 +1,47 @@
 pub mod idt;
+pub mod pic;
+pub mod timer;
 
-use crate::println;
+use crate::driver::{keyboard, serial};
+use crate::nucleus::debug;
 use x86_64::structures::idt::{InterruptDescriptorTable, InterruptStackFrame};
+use x86_64::VirtAddr;
 
-extern "x86-interrupt" fn breakpoint_handler(stack_frame: InterruptStackFrame) {
-    println!("EXCEPTION: BREAKPOINT\n{:#?}", stack_frame);
-}
+/// Software interrupt with an empty handler, used to measure interrupt entry and exit.
+pub const NOOP_VECTOR: u8 = 0xf0;
+
+extern "x86-interrupt" fn noop_handler(_stack_frame: InterruptStackFrame) {}
 
 use lazy_static::lazy_static;
 
 lazy_static! {
     static ref IDT: InterruptDescriptorTable = {
         let mut idt = InterruptDescriptorTable::new();
-        idt.breakpoint.set_handler_fn(breakpoint_handler);
+        unsafe {
+            idt.debug
+                .set_handler_addr(VirtAddr::new(debug::debug_entry as *const () as u64));
+            idt.breakpoint
+                .set_handler_addr(VirtAddr::new(debug::breakpoint_entry as *const () as u64));
+            idt.divide_error
+                .set_handler_addr(VirtAddr::new(debug::divide_error_entry as *const () as u64));
+            idt.invalid_opcode.set_handler_addr(VirtAddr::new(
+                debug::invalid_opcode_entry as *const () as u64,
+            ));
+            idt.double_fault
+                .set_handler_addr(VirtAddr::new(debug::double_fault_entry as *const () as u64));
+            idt.general_protection_fault.set_handler_addr(VirtAddr::new(
+                debug::general_protection_entry as *const () as u64,
+            ));
+            idt.page_fault
+                .set_handler_addr(VirtAddr::new(debug::page_fault_entry as *const () as u64));
+        }
+        idt[pic::vector(timer::IRQ)].set_handler_fn(timer::interrupt_handler);
+        idt[pic::vector(keyboard::IRQ)].set_handler_fn(keyboard::interrupt_handler);
+        idt[pic::vector(serial::ComPort::Com1.irq())]
+            .set_handler_fn(serial::com1_com3_interrupt_handler);
+        idt[pic::vector(serial::ComPort::Com2.irq())]
+            .set_handler_fn(serial::com2_com4_interrupt_handler);
+        idt[NOOP_VECTOR].set_handler_fn(noop_handler);
         idt
     };
 }
@@ -20,3 +49,19 @@ lazy_static! {
 pub fn init_idt() {
     IDT.load();
 }
+
+#[cfg(all(test, target_os = "none"))]
+mod tests {
+    use super::*;
+    use crate::libs::testing::{kernel_bench, Bencher};
+
+    #[test_case]
+    fn test_noop_interrupt() {
+        unsafe { core::arch::asm!("int {vector}", vector = const NOOP_VECTOR) };
+    }
+
+    #[kernel_bench]
+    fn bench_interrupt_latency(bencher: &mut Bencher) {
+        bencher.iter(|| unsafe { core::arch::asm!("int {vector}", vector = const NOOP_VECTOR) });
+    }
+}
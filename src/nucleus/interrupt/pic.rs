@@ -0,0 +1,69 @@
+use x86_64::instructions::port::Port;
+
+const PRIMARY_COMMAND: u16 = 0x20;
+const PRIMARY_DATA: u16 = 0x21;
+const SECONDARY_COMMAND: u16 = 0xa0;
+const SECONDARY_DATA: u16 = 0xa1;
+const WAIT_PORT: u16 = 0x80;
+
+const INITIALIZE: u8 = 0x11;
+const MODE_8086: u8 = 0x01;
+const END_OF_INTERRUPT: u8 = 0x20;
+const CASCADE_IRQ: u8 = 2;
+
+pub const PRIMARY_OFFSET: u8 = 32;
+pub const SECONDARY_OFFSET: u8 = PRIMARY_OFFSET + 8;
+
+pub fn init(enabled: u16) {
+    write(PRIMARY_COMMAND, INITIALIZE);
+    write(SECONDARY_COMMAND, INITIALIZE);
+
+    write(PRIMARY_DATA, PRIMARY_OFFSET);
+    write(SECONDARY_DATA, SECONDARY_OFFSET);
+
+    write(PRIMARY_DATA, 1 << CASCADE_IRQ);
+    write(SECONDARY_DATA, CASCADE_IRQ);
+
+    write(PRIMARY_DATA, MODE_8086);
+    write(SECONDARY_DATA, MODE_8086);
+
+    set_enabled(enabled);
+}
+
+pub fn set_enabled(enabled: u16) {
+    let enabled = match enabled >> 8 {
+        0 => enabled,
+        _ => enabled | 1 << CASCADE_IRQ,
+    };
+
+    write(PRIMARY_DATA, !enabled as u8);
+    write(SECONDARY_DATA, !(enabled >> 8) as u8);
+}
+
+pub fn enable(irq: u8) {
+    let enabled = !(read(PRIMARY_DATA) as u16 | (read(SECONDARY_DATA) as u16) << 8);
+    set_enabled(enabled | 1 << irq);
+}
+
+pub const fn vector(irq: u8) -> u8 {
+    PRIMARY_OFFSET + irq
+}
+
+pub fn end_of_interrupt(irq: u8) {
+    if irq >= 8 {
+        write(SECONDARY_COMMAND, END_OF_INTERRUPT);
+    }
+
+    write(PRIMARY_COMMAND, END_OF_INTERRUPT);
+}
+
+fn read(port: u16) -> u8 {
+    unsafe { Port::<u8>::new(port).read() }
+}
+
+fn write(port: u16, value: u8) {
+    unsafe {
+        Port::<u8>::new(port).write(value);
+        Port::<u8>::new(WAIT_PORT).write(0);
+    }
+}
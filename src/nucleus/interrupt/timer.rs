@@ -0,0 +1,79 @@
+use super::pic;
+use core::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use x86_64::instructions::port::Port;
+use x86_64::structures::idt::InterruptStackFrame;
+
+const PIT_FREQUENCY: u32 = 1_193_182;
+const PIT_CHANNEL_0: u16 = 0x40;
+const PIT_COMMAND: u16 = 0x43;
+const PIT_RATE_GENERATOR: u8 = 0x34;
+
+pub const IRQ: u8 = 0;
+
+static TICKS: AtomicU64 = AtomicU64::new(0);
+static FREQUENCY: AtomicU32 = AtomicU32::new(0);
+static HANDLER: spin::Mutex<Option<fn(u64)>> = spin::Mutex::new(None);
+
+pub fn init(frequency: u32) {
+    let divisor = (PIT_FREQUENCY / frequency).clamp(1, u16::MAX as u32) as u16;
+
+    unsafe {
+        Port::<u8>::new(PIT_COMMAND).write(PIT_RATE_GENERATOR);
+        Port::<u8>::new(PIT_CHANNEL_0).write(divisor as u8);
+        Port::<u8>::new(PIT_CHANNEL_0).write((divisor >> 8) as u8);
+    }
+
+    FREQUENCY.store(PIT_FREQUENCY / divisor as u32, Ordering::SeqCst);
+}
+
+pub fn ticks() -> u64 {
+    TICKS.load(Ordering::SeqCst)
+}
+
+pub fn frequency() -> u32 {
+    FREQUENCY.load(Ordering::SeqCst)
+}
+
+pub fn milliseconds_to_ticks(milliseconds: u64) -> u64 {
+    let ticks = milliseconds * frequency() as u64 / 1_000;
+    core::cmp::max(ticks, 1)
+}
+
+pub fn set_handler(handler: Option<fn(u64)>) {
+    x86_64::instructions::interrupts::without_interrupts(|| *HANDLER.lock() = handler);
+}
+
+pub(super) extern "x86-interrupt" fn interrupt_handler(_stack_frame: InterruptStackFrame) {
+    let ticks = TICKS.fetch_add(1, Ordering::SeqCst) + 1;
+    pic::end_of_interrupt(IRQ);
+
+    let handler = HANDLER.try_lock().and_then(|handler| *handler);
+
+    if let Some(handler) = handler {
+        handler(ticks);
+    }
+}
+
+#[cfg(all(test, target_os = "none"))]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_ticks_advance() {
+        let start = ticks();
+
+        while ticks() < start + 2 {
+            core::hint::spin_loop();
+        }
+
+        assert!(ticks() >= start + 2);
+    }
+
+    #[test_case]
+    fn test_milliseconds_to_ticks() {
+        let frequency = frequency() as u64;
+
+        assert_eq!(milliseconds_to_ticks(1_000), frequency);
+        assert_eq!(milliseconds_to_ticks(0), 1);
+    }
+}
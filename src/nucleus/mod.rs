@@ -1 +1,4 @@
+pub mod clock;
+pub mod debug;
 pub mod interrupt;
+pub mod sync;
@@ -0,0 +1,186 @@
+use core::mem::ManuallyDrop;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{AtomicU32, Ordering};
+use x86_64::instructions::interrupts;
+
+const NO_OWNER: u32 = u32::MAX;
+
+/// The lock is already held by the calling CPU, for example by the code an interrupt handler
+/// interrupted. Waiting for it would never end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reentrant;
+
+/// A spin lock that keeps interrupts disabled while held, so an interrupt handler can never spin
+/// on a lock held by the code it interrupted. Locking it again from the owning CPU, which can
+/// still happen from exception handlers, is reported instead of deadlocking.
+pub struct IrqMutex<T> {
+    inner: spin::Mutex<T>,
+    owner: AtomicU32,
+}
+
+impl<T> IrqMutex<T> {
+    pub const fn new(value: T) -> Self {
+        Self {
+            inner: spin::Mutex::new(value),
+            owner: AtomicU32::new(NO_OWNER),
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the calling CPU already holds the lock.
+    pub fn lock(&self) -> IrqMutexGuard<'_, T> {
+        match self.lock_checked() {
+            Ok(guard) => guard,
+            Err(Reentrant) => panic!("re-entrant lock on cpu {}", current_cpu()),
+        }
+    }
+
+    pub fn lock_checked(&self) -> Result<IrqMutexGuard<'_, T>, Reentrant> {
+        let enable_interrupts = interrupts::are_enabled();
+        interrupts::disable();
+
+        let cpu = current_cpu();
+
+        if self.owner.load(Ordering::Acquire) == cpu {
+            if enable_interrupts {
+                interrupts::enable();
+            }
+
+            return Err(Reentrant);
+        }
+
+        let guard = self.inner.lock();
+        self.owner.store(cpu, Ordering::Release);
+
+        Ok(IrqMutexGuard {
+            guard: ManuallyDrop::new(guard),
+            owner: &self.owner,
+            enable_interrupts,
+        })
+    }
+
+    pub fn try_lock(&self) -> Option<IrqMutexGuard<'_, T>> {
+        let enable_interrupts = interrupts::are_enabled();
+        interrupts::disable();
+
+        match self.inner.try_lock() {
+            Some(guard) => {
+                self.owner.store(current_cpu(), Ordering::Release);
+
+                Some(IrqMutexGuard {
+                    guard: ManuallyDrop::new(guard),
+                    owner: &self.owner,
+                    enable_interrupts,
+                })
+            }
+            None => {
+                if enable_interrupts {
+                    interrupts::enable();
+                }
+
+                None
+            }
+        }
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.inner.is_locked()
+    }
+
+    /// Releases a lock held by code that will never resume. Interrupts are left as they are.
+    ///
+    /// # Safety
+    ///
+    /// Nothing may still be using the guarded value.
+    pub unsafe fn force_unlock(&self) {
+        if self.inner.is_locked() {
+            self.owner.store(NO_OWNER, Ordering::Release);
+            self.inner.force_unlock();
+        }
+    }
+}
+
+pub struct IrqMutexGuard<'a, T> {
+    guard: ManuallyDrop<spin::MutexGuard<'a, T>>,
+    owner: &'a AtomicU32,
+    enable_interrupts: bool,
+}
+
+impl<T> Deref for IrqMutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> DerefMut for IrqMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+impl<T> Drop for IrqMutexGuard<'_, T> {
+    fn drop(&mut self) {
+        self.owner.store(NO_OWNER, Ordering::Release);
+        unsafe { ManuallyDrop::drop(&mut self.guard) };
+
+        if self.enable_interrupts {
+            interrupts::enable();
+        }
+    }
+}
+
+/// The kernel only runs on the boot CPU until SMP exists. Reading the APIC ID would cost a
+/// `cpuid`, which is a VM exit under virtualization, on every lock.
+pub const BOOT_CPU: u32 = 0;
+
+/// ID of the calling CPU, used as the owner of an `IrqMutex`.
+pub fn current_cpu() -> u32 {
+    BOOT_CPU
+}
+
+#[cfg(all(test, target_os = "none"))]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_lock_disables_interrupts() {
+        let mutex = IrqMutex::new(0);
+        let enabled = interrupts::are_enabled();
+
+        {
+            let mut guard = mutex.lock();
+            *guard += 1;
+            assert!(!interrupts::are_enabled());
+        }
+
+        assert_eq!(interrupts::are_enabled(), enabled);
+        assert_eq!(*mutex.lock(), 1);
+    }
+
+    #[test_case]
+    fn test_reentrant_lock() {
+        let mutex = IrqMutex::new(());
+        let _guard = mutex.lock();
+
+        assert_eq!(mutex.lock_checked().err(), Some(Reentrant));
+        assert!(mutex.try_lock().is_none());
+    }
+
+    #[test_case]
+    fn test_force_unlock() {
+        let mutex = IrqMutex::new(());
+        let enabled = interrupts::are_enabled();
+        core::mem::forget(mutex.lock());
+
+        unsafe { mutex.force_unlock() };
+
+        if enabled {
+            interrupts::enable();
+        }
+
+        assert!(mutex.lock_checked().is_ok());
+    }
+}
@@ -20,3 +20,8 @@ fn panic(info: &core::panic::PanicInfo) -> ! {
 fn test_basic_boot() {
     ferros::println!("Boot successful!");
 }
+
+#[test_case]
+fn test_serial_println() {
+    ferros::serial_println!("Serial output from an integration test");
+}
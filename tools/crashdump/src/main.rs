@@ -0,0 +1,320 @@
+#[allow(dead_code)]
+#[path = "../../../src/nucleus/debug/crashdump/format.rs"]
+mod format;
+
+use format::{read_u16, read_u32, read_u64, Record, RecordKind, BEGIN, END, REGISTER_NAMES};
+use std::io::Read;
+use std::process::ExitCode;
+
+const IDT_ENTRY_SIZE: usize = 16;
+const GDT_ENTRY_SIZE: usize = 8;
+
+fn main() -> ExitCode {
+    let path = match std::env::args().nth(1) {
+        Some(path) => path,
+        None => {
+            eprintln!("usage: crashdump <dump or serial log, - for stdin>");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let input = match read_input(&path) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("{}: {}", path, error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let dump = match unarmor(&input) {
+        Some(dump) => dump,
+        None => {
+            eprintln!("{}: invalid base64 between the dump markers", path);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match format::parse(&dump) {
+        Ok(records) => {
+            records.for_each(print_record);
+            println!("checksum ok");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{}: {}", path, error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn read_input(path: &str) -> std::io::Result<Vec<u8>> {
+    match path {
+        "-" => {
+            let mut input = Vec::new();
+            std::io::stdin().read_to_end(&mut input)?;
+            Ok(input)
+        }
+        _ => std::fs::read(path),
+    }
+}
+
+/// Cuts the last armored dump out of a serial log. Input without markers is a raw dump.
+fn unarmor(input: &[u8]) -> Option<Vec<u8>> {
+    let text = String::from_utf8_lossy(input);
+    let start = match text.rfind(BEGIN) {
+        Some(start) => start + BEGIN.len(),
+        None => return Some(input.to_vec()),
+    };
+
+    let body = &text[start..];
+    let body = &body[..body.find(END).unwrap_or(body.len())];
+
+    let mut dump = vec![0; body.len() / 4 * 3 + 3];
+    let length = format::decode_base64(body, &mut dump)?;
+    dump.truncate(length);
+
+    Some(dump)
+}
+
+fn print_record(record: Record) {
+    let payload = record.payload;
+
+    match RecordKind::from_u16(record.kind) {
+        Some(RecordKind::Reason) => println!("reason: {}\n", String::from_utf8_lossy(payload)),
+        Some(RecordKind::Registers) => print_registers(payload),
+        Some(RecordKind::Stack) => {
+            let address = read_u64(payload).unwrap_or(0);
+            println!("stack:");
+            print_hexdump(address, payload.get(8..).unwrap_or(&[]));
+        }
+        Some(RecordKind::Backtrace) => print_backtrace(payload),
+        Some(RecordKind::Log) => {
+            println!("log:");
+            println!("{}\n", String::from_utf8_lossy(payload).trim_end());
+        }
+        Some(RecordKind::InterruptTable) => print_interrupt_table(payload),
+        Some(RecordKind::GlobalTable) => print_global_table(payload),
+        Some(RecordKind::Memory) => print_memory(payload),
+        Some(RecordKind::End) | None => println!(
+            "skipped record of kind {:#x}, {} bytes\n",
+            record.kind,
+            payload.len()
+        ),
+    }
+}
+
+fn print_registers(payload: &[u8]) {
+    println!("registers:");
+
+    for (row, names) in REGISTER_NAMES.chunks(3).enumerate() {
+        for (column, name) in names.iter().enumerate() {
+            let index = row * 3 + column;
+
+            match payload.get(index * 8..).and_then(read_u64) {
+                Some(value) => print!("  {:>6} {:016x}", name, value),
+                None => print!("  {:>6} ????????????????", name),
+            }
+        }
+
+        println!();
+    }
+
+    println!();
+}
+
+fn print_hexdump(address: u64, bytes: &[u8]) {
+    if bytes.is_empty() {
+        println!("  (unmapped)\n");
+        return;
+    }
+
+    for (line, chunk) in bytes.chunks(16).enumerate() {
+        let hex = chunk
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let text = chunk
+            .iter()
+            .map(|byte| match byte {
+                b' '..=b'~' => *byte as char,
+                _ => '.',
+            })
+            .collect::<String>();
+
+        println!("  {:016x}  {:<47}  {}", address + line as u64 * 16, hex, text);
+    }
+
+    println!();
+}
+
+fn print_backtrace(mut payload: &[u8]) {
+    println!("backtrace:");
+
+    let mut index = 0;
+
+    while let (Some(address), Some(offset), Some(length)) = (
+        read_u64(payload),
+        payload.get(8..).and_then(read_u64),
+        payload.get(16..).and_then(read_u16),
+    ) {
+        let end = 18 + length as usize;
+        let name = match payload.get(18..end) {
+            Some(name) => String::from_utf8_lossy(name),
+            None => break,
+        };
+
+        match name.is_empty() {
+            true => println!("  #{:<2} {:016x}  <unknown>", index, address),
+            false => println!("  #{:<2} {:016x}  {}+{:#x}", index, address, name, offset),
+        }
+
+        payload = &payload[end..];
+        index += 1;
+    }
+
+    println!();
+}
+
+fn print_interrupt_table(payload: &[u8]) {
+    let base = read_u64(payload).unwrap_or(0);
+    let table = payload.get(8..).unwrap_or(&[]);
+    println!("interrupt descriptor table at {:#x}:", base);
+
+    for (vector, entry) in table.chunks_exact(IDT_ENTRY_SIZE).enumerate() {
+        if let Some(gate) = decode_gate(entry) {
+            println!(
+                "  {:>3}  {:016x}  selector {:#06x}  {}  dpl {}  ist {}",
+                vector,
+                gate.handler,
+                gate.selector,
+                if gate.trap { "trap     " } else { "interrupt" },
+                gate.privilege_level,
+                gate.stack_index,
+            );
+        }
+    }
+
+    println!();
+}
+
+fn print_global_table(payload: &[u8]) {
+    let base = read_u64(payload).unwrap_or(0);
+    let table = payload.get(8..).unwrap_or(&[]);
+    println!("global descriptor table at {:#x}:", base);
+
+    for (index, entry) in table.chunks_exact(GDT_ENTRY_SIZE).enumerate() {
+        let descriptor = read_u64(entry).unwrap_or(0);
+        println!(
+            "  {:>3}  {:016x}  {}",
+            index,
+            descriptor,
+            describe_segment(descriptor)
+        );
+    }
+
+    println!();
+}
+
+fn print_memory(payload: &[u8]) {
+    let address = read_u64(payload).unwrap_or(0);
+    let length = payload.get(8..).and_then(read_u16).unwrap_or(0) as usize;
+    let name = payload.get(10..10 + length).unwrap_or(&[]);
+
+    println!(
+        "memory {} at {:#x}:",
+        String::from_utf8_lossy(name),
+        address
+    );
+    print_hexdump(address, payload.get(10 + length..).unwrap_or(&[]));
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Gate {
+    handler: u64,
+    selector: u16,
+    stack_index: u8,
+    trap: bool,
+    privilege_level: u8,
+}
+
+/// Decodes a present 64-bit interrupt or trap gate.
+fn decode_gate(entry: &[u8]) -> Option<Gate> {
+    let options = read_u16(entry.get(4..)?)?;
+
+    if options & 0x8000 == 0 {
+        return None;
+    }
+
+    let low = read_u16(entry)? as u64;
+    let middle = read_u16(entry.get(6..)?)? as u64;
+    let high = read_u32(entry.get(8..)?)? as u64;
+
+    Some(Gate {
+        handler: high << 32 | middle << 16 | low,
+        selector: read_u16(entry.get(2..)?)?,
+        stack_index: (options & 0x7) as u8,
+        trap: options & 0x0100 != 0,
+        privilege_level: (options >> 13 & 0x3) as u8,
+    })
+}
+
+fn describe_segment(descriptor: u64) -> String {
+    let access = descriptor >> 40 & 0xff;
+
+    if access & 0x80 == 0 {
+        return String::from("not present");
+    }
+
+    let privilege_level = access >> 5 & 0x3;
+
+    let kind = match (access & 0x10 != 0, access & 0x08 != 0) {
+        (false, _) => "system",
+        (true, true) if descriptor & 1 << 53 != 0 => "code64",
+        (true, true) => "code",
+        (true, false) => "data",
+    };
+
+    format!("{} dpl {}", kind, privilege_level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unarmor() {
+        let log = format!("booting\n{}\r\nRkVS\r\nUk9T\n{}\nhalted\n", BEGIN, END);
+
+        assert_eq!(unarmor(log.as_bytes()).unwrap(), b"FERROS");
+        assert_eq!(unarmor(b"FERROSCD").unwrap(), b"FERROSCD");
+    }
+
+    #[test]
+    fn test_decode_gate() {
+        let mut entry = [0; IDT_ENTRY_SIZE];
+        entry[..2].copy_from_slice(&0x5678u16.to_le_bytes());
+        entry[2..4].copy_from_slice(&0x08u16.to_le_bytes());
+        entry[4..6].copy_from_slice(&0xef01u16.to_le_bytes());
+        entry[6..8].copy_from_slice(&0x1234u16.to_le_bytes());
+
+        assert_eq!(
+            decode_gate(&entry),
+            Some(Gate {
+                handler: 0x1234_5678,
+                selector: 0x08,
+                stack_index: 1,
+                trap: true,
+                privilege_level: 3,
+            })
+        );
+        assert_eq!(decode_gate(&[0; IDT_ENTRY_SIZE]), None);
+    }
+
+    #[test]
+    fn test_describe_segment() {
+        assert_eq!(describe_segment(0x0020_9a00_0000_0000), "code64 dpl 0");
+        assert_eq!(describe_segment(0x0000_f200_0000_0000), "data dpl 3");
+        assert_eq!(describe_segment(0), "not present");
+    }
+}